@@ -1,4 +1,9 @@
-import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
 import {
   withdrawTokensInstruction,
   createNftInstruction,
@@ -8,6 +13,7 @@ import {
 } from "./raw_instructions";
 import {
   COLLECTION_PREFIX,
+  FEE_VAULT_PREFIX,
   MINT_PREFIX,
   NftRecord,
   METADATA_SIGNER,
@@ -49,6 +55,33 @@ export const getMasterEditionPda = (mint: PublicKey) => {
   )[0];
 };
 
+/**
+ * Returns the account paying a protocol fee and the fee vault of the program
+ * @param programId The Name tokenizer program ID
+ * @param centralKey The central state key
+ * @param feeMint The mint of the fees (the default key for SOL fees)
+ * @param payer The wallet paying the fee
+ * @returns
+ */
+export const getFeeAccounts = (
+  programId: PublicKey,
+  centralKey: PublicKey,
+  feeMint: PublicKey,
+  payer: PublicKey
+) => {
+  if (feeMint.equals(PublicKey.default)) {
+    return { feeSource: payer, feeVault: centralKey };
+  }
+  const [feeVaultAuthority] = PublicKey.findProgramAddressSync(
+    [FEE_VAULT_PREFIX, feeMint.toBuffer()],
+    programId
+  );
+  return {
+    feeSource: getAssociatedTokenAddressSync(feeMint, payer),
+    feeVault: getAssociatedTokenAddressSync(feeMint, feeVaultAuthority, true),
+  };
+};
+
 /**
  * Mainnet program ID
  */
@@ -149,6 +182,11 @@ export const createCollection = (feePayer: PublicKey, programId: PublicKey) => {
  * @param nameOwner The owner of the domain name to tokenize
  * @param feePayer The fee payer of the transaction
  * @param programId The Name tokenizer program ID
+ * @param creatorShare Share of the royalties (in percent) of the name owner
+ * @param soulbound Freeze the NFT in the token account of the name owner
+ * @param wipeData Zero the data of the domain once it is held by the NFT record
+ * @param feeMint The mint of the protocol fees (the default key for SOL fees)
+ * @param referrer The referrer receiving a share of the protocol fee
  * @returns
  */
 export const createNft = (
@@ -157,7 +195,12 @@ export const createNft = (
   nameAccount: PublicKey,
   nameOwner: PublicKey,
   feePayer: PublicKey,
-  programId: PublicKey
+  programId: PublicKey,
+  creatorShare = 0,
+  soulbound = false,
+  wipeData = false,
+  feeMint = PublicKey.default,
+  referrer?: PublicKey
 ) => {
   const [centralKey] = PublicKey.findProgramAddressSync(
     [programId.toBuffer()],
@@ -183,8 +226,22 @@ export const createNft = (
     METADATA_ID
   );
   const editionAccount = getMasterEditionPda(collectionMint);
+  const nftEdition = getMasterEditionPda(mint);
+  const nftEscrow = getAssociatedTokenAddressSync(mint, centralKey, true);
+  const { feeSource, feeVault } = getFeeAccounts(
+    programId,
+    centralKey,
+    feeMint,
+    feePayer
+  );
 
-  const ix = new createNftInstruction({ name, uri }).getInstruction(
+  const ix = new createNftInstruction({
+    name,
+    uri,
+    creatorShare,
+    soulbound,
+    wipeData,
+  }).getInstruction(
     programId,
     mint,
     nftDestination,
@@ -202,8 +259,19 @@ export const createNft = (
     SystemProgram.programId,
     NAME_PROGRAM_ID,
     SYSVAR_RENT_PUBKEY,
-    METADATA_SIGNER
+    nftEdition,
+    nftEscrow,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
+    METADATA_SIGNER,
+    feeSource,
+    feeVault,
+    referrer ?? feeVault
   );
+  // The metadata signer only co-signs custom URIs
+  ix.keys
+    .filter((k) => k.pubkey.equals(METADATA_SIGNER))
+    .forEach((k) => (k.isSigner = !!uri));
 
   return [ix];
 };
@@ -213,13 +281,24 @@ export const createNft = (
  * @param nameAccount The domain name key
  * @param nftOwner The owner of the NFT to redeem
  * @param programId The Name tokenizer program ID
+ * @param wipeData Zero the data of the domain before it is returned to the NFT owner
+ * @param feeMint The mint of the protocol fees (the default key for SOL fees)
+ * @param referrer The referrer receiving a share of the protocol fee
  * @returns
  */
 export const redeemNft = (
   nameAccount: PublicKey,
   nftOwner: PublicKey,
-  programId: PublicKey
+  programId: PublicKey,
+  wipeData = false,
+  feeMint = PublicKey.default,
+  referrer?: PublicKey
 ) => {
+  const [centralKey] = PublicKey.findProgramAddressSync(
+    [programId.toBuffer()],
+    programId
+  );
+
   const [mint] = PublicKey.findProgramAddressSync(
     [MINT_PREFIX, nameAccount.toBuffer()],
     programId
@@ -227,8 +306,16 @@ export const redeemNft = (
 
   const [nftRecord] = NftRecord.findKeySync(nameAccount, programId);
   const nftSource = getAssociatedTokenAddressSync(mint, nftOwner);
+  const nftEscrow = getAssociatedTokenAddressSync(mint, centralKey, true);
+  const nftEdition = getMasterEditionPda(mint);
+  const { feeSource, feeVault } = getFeeAccounts(
+    programId,
+    centralKey,
+    feeMint,
+    nftOwner
+  );
 
-  const ix = new redeemNftInstruction().getInstruction(
+  const ix = new redeemNftInstruction({ wipeData }).getInstruction(
     programId,
     mint,
     nftSource,
@@ -236,7 +323,15 @@ export const redeemNft = (
     nftRecord,
     nameAccount,
     TOKEN_PROGRAM_ID,
-    NAME_PROGRAM_ID
+    NAME_PROGRAM_ID,
+    nftEscrow,
+    centralKey,
+    SystemProgram.programId,
+    feeSource,
+    feeVault,
+    referrer ?? feeVault,
+    nftEdition,
+    METADATA_ID
   );
 
   return [ix];
@@ -31,7 +31,8 @@ export class unverifyNftInstruction {
     metadataProgram: PublicKey,
     systemProgram: PublicKey,
     rentAccount: PublicKey,
-    metadataSigner: PublicKey
+    metadataSigner: PublicKey,
+    tldRecord?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -85,6 +86,13 @@ export class unverifyNftInstruction {
       isSigner: true,
       isWritable: false,
     });
+    if (!!tldRecord) {
+      keys.push({
+        pubkey: tldRecord,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -118,7 +126,8 @@ export class editDataInstruction {
     nftRecord: PublicKey,
     nameAccount: PublicKey,
     splTokenProgram: PublicKey,
-    splNameServiceProgram: PublicKey
+    splNameServiceProgram: PublicKey,
+    nameClass?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -152,6 +161,13 @@ export class editDataInstruction {
       isSigner: false,
       isWritable: false,
     });
+    if (!!nameClass) {
+      keys.push({
+        pubkey: nameClass,
+        isSigner: true,
+        isWritable: false,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -252,7 +268,8 @@ export class createCollectionInstruction {
     systemProgram: PublicKey,
     splNameServiceProgram: PublicKey,
     ataProgram: PublicKey,
-    rentAccount: PublicKey
+    rentAccount: PublicKey,
+    tldRecord?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -316,6 +333,13 @@ export class createCollectionInstruction {
       isSigner: false,
       isWritable: false,
     });
+    if (!!tldRecord) {
+      keys.push({
+        pubkey: tldRecord,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -327,17 +351,32 @@ export class createNftInstruction {
   tag: number;
   name: string;
   uri: string;
+  creatorShare: number;
+  soulbound: boolean;
+  wipeData: boolean;
   static schema = {
     struct: {
       tag: "u8",
       name: "string",
       uri: "string",
+      creatorShare: "u8",
+      soulbound: "bool",
+      wipeData: "bool",
     },
   };
-  constructor(obj: { name: string; uri: string }) {
+  constructor(obj: {
+    name: string;
+    uri: string;
+    creatorShare: number;
+    soulbound: boolean;
+    wipeData: boolean;
+  }) {
     this.tag = 2;
     this.name = obj.name;
     this.uri = obj.uri;
+    this.creatorShare = obj.creatorShare;
+    this.soulbound = obj.soulbound;
+    this.wipeData = obj.wipeData;
   }
   serialize(): Uint8Array {
     return serialize(createNftInstruction.schema, this);
@@ -360,7 +399,19 @@ export class createNftInstruction {
     systemProgram: PublicKey,
     splNameServiceProgram: PublicKey,
     rentAccount: PublicKey,
-    metadataSigner: PublicKey
+    nftEdition: PublicKey,
+    nftEscrow: PublicKey,
+    ataProgram: PublicKey,
+    instructionsSysvar: PublicKey,
+    metadataSigner: PublicKey,
+    feeSource: PublicKey,
+    feeVault: PublicKey,
+    referrer: PublicKey,
+    tldRecord?: PublicKey,
+    nameClass?: PublicKey,
+    parentName?: PublicKey,
+    parentNftRecord?: PublicKey,
+    parentNftAccount?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -444,11 +495,92 @@ export class createNftInstruction {
       isSigner: false,
       isWritable: false,
     });
+    keys.push({
+      pubkey: nftEdition,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nftEscrow,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: ataProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: instructionsSysvar,
+      isSigner: false,
+      isWritable: false,
+    });
     keys.push({
       pubkey: metadataSigner,
-      isSigner: true,
+      isSigner: false,
       isWritable: false,
     });
+    keys.push({
+      pubkey: feeSource,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: feeVault,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: referrer,
+      isSigner: false,
+      isWritable: true,
+    });
+    if (
+      !!tldRecord ||
+      !!nameClass ||
+      !!parentName ||
+      !!parentNftRecord ||
+      !!parentNftAccount
+    ) {
+      keys.push({
+        pubkey: tldRecord ?? programId,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (
+      !!nameClass ||
+      !!parentName ||
+      !!parentNftRecord ||
+      !!parentNftAccount
+    ) {
+      keys.push({
+        pubkey: nameClass ?? programId,
+        isSigner: !!nameClass,
+        isWritable: false,
+      });
+    }
+    if (!!parentName || !!parentNftRecord || !!parentNftAccount) {
+      keys.push({
+        pubkey: parentName ?? programId,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!parentNftRecord || !!parentNftAccount) {
+      keys.push({
+        pubkey: parentNftRecord ?? programId,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!parentNftAccount) {
+      keys.push({
+        pubkey: parentNftAccount,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -525,13 +657,16 @@ export class createMintInstruction {
 }
 export class redeemNftInstruction {
   tag: number;
+  wipeData: boolean;
   static schema = {
     struct: {
       tag: "u8",
+      wipeData: "bool",
     },
   };
-  constructor() {
+  constructor(obj: { wipeData: boolean }) {
     this.tag = 3;
+    this.wipeData = obj.wipeData;
   }
   serialize(): Uint8Array {
     return serialize(redeemNftInstruction.schema, this);
@@ -544,7 +679,16 @@ export class redeemNftInstruction {
     nftRecord: PublicKey,
     nameAccount: PublicKey,
     splTokenProgram: PublicKey,
-    splNameServiceProgram: PublicKey
+    splNameServiceProgram: PublicKey,
+    nftEscrow: PublicKey,
+    centralState: PublicKey,
+    systemProgram: PublicKey,
+    feeSource: PublicKey,
+    feeVault: PublicKey,
+    referrer: PublicKey,
+    nftEdition: PublicKey,
+    metadataProgram: PublicKey,
+    nameClass?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -583,6 +727,1005 @@ export class redeemNftInstruction {
       isSigner: false,
       isWritable: false,
     });
+    keys.push({
+      pubkey: nftEscrow,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feeSource,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: feeVault,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: referrer,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nftEdition,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: metadataProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    if (!!nameClass) {
+      keys.push({
+        pubkey: nameClass,
+        isSigner: true,
+        isWritable: false,
+      });
+    }
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class registerTldInstruction {
+  tag: number;
+  collectionName: string;
+  collectionUri: string;
+  symbol: string;
+  static schema = {
+    struct: {
+      tag: "u8",
+      collectionName: "string",
+      collectionUri: "string",
+      symbol: "string",
+    },
+  };
+  constructor(obj: {
+    collectionName: string;
+    collectionUri: string;
+    symbol: string;
+  }) {
+    this.tag = 7;
+    this.collectionName = obj.collectionName;
+    this.collectionUri = obj.collectionUri;
+    this.symbol = obj.symbol;
+  }
+  serialize(): Uint8Array {
+    return serialize(registerTldInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    tldRecord: PublicKey,
+    tld: PublicKey,
+    programData: PublicKey,
+    admin: PublicKey,
+    feePayer: PublicKey,
+    systemProgram: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: tldRecord,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: tld,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: programData,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: admin,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class updateCentralStateInstruction {
+  tag: number;
+  baseUri: string;
+  uriDerivation: number;
+  maxCreatorShare: number;
+  fees: {
+    mint: Uint8Array;
+    tokenizationFee: bigint;
+    redemptionFee: bigint;
+    referrerShare: number;
+  };
+  allowedPrograms: Uint8Array[];
+  static schema = {
+    struct: {
+      tag: "u8",
+      baseUri: "string",
+      uriDerivation: "u8",
+      maxCreatorShare: "u8",
+      fees: {
+        struct: {
+          mint: { array: { type: "u8", len: 32 } },
+          tokenizationFee: "u64",
+          redemptionFee: "u64",
+          referrerShare: "u8",
+        },
+      },
+      allowedPrograms: { array: { type: { array: { type: "u8", len: 32 } } } },
+    },
+  };
+  constructor(obj: {
+    baseUri: string;
+    uriDerivation: number;
+    maxCreatorShare: number;
+    fees: {
+      mint: Uint8Array;
+      tokenizationFee: bigint;
+      redemptionFee: bigint;
+      referrerShare: number;
+    };
+    allowedPrograms: Uint8Array[];
+  }) {
+    this.tag = 8;
+    this.baseUri = obj.baseUri;
+    this.uriDerivation = obj.uriDerivation;
+    this.maxCreatorShare = obj.maxCreatorShare;
+    this.fees = obj.fees;
+    this.allowedPrograms = obj.allowedPrograms;
+  }
+  serialize(): Uint8Array {
+    return serialize(updateCentralStateInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    centralState: PublicKey,
+    programData: PublicKey,
+    admin: PublicKey,
+    feePayer: PublicKey,
+    systemProgram: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: programData,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: admin,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class editCreatorsInstruction {
+  tag: number;
+  creator: Uint8Array;
+  share: number;
+  static schema = {
+    struct: {
+      tag: "u8",
+      creator: { array: { type: "u8", len: 32 } },
+      share: "u8",
+    },
+  };
+  constructor(obj: { creator: Uint8Array; share: number }) {
+    this.tag = 9;
+    this.creator = obj.creator;
+    this.share = obj.share;
+  }
+  serialize(): Uint8Array {
+    return serialize(editCreatorsInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    nftOwner: PublicKey,
+    nftAccount: PublicKey,
+    nftRecord: PublicKey,
+    nameAccount: PublicKey,
+    metadataAccount: PublicKey,
+    centralState: PublicKey,
+    metadataProgram: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: nftOwner,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftRecord,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nameAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: metadataAccount,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: metadataProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class withdrawFeesInstruction {
+  tag: number;
+  static schema = {
+    struct: {
+      tag: "u8",
+    },
+  };
+  constructor() {
+    this.tag = 10;
+  }
+  serialize(): Uint8Array {
+    return serialize(withdrawFeesInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    centralState: PublicKey,
+    feeVault: PublicKey,
+    destination: PublicKey,
+    programData: PublicKey,
+    admin: PublicKey,
+    splTokenProgram: PublicKey,
+    feeVaultAuthority?: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: feeVault,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: destination,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: programData,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: admin,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: splTokenProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    if (!!feeVaultAuthority) {
+      keys.push({
+        pubkey: feeVaultAuthority,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class setLockAuthorityInstruction {
+  tag: number;
+  lockAuthority: Uint8Array;
+  static schema = {
+    struct: {
+      tag: "u8",
+      lockAuthority: { array: { type: "u8", len: 32 } },
+    },
+  };
+  constructor(obj: { lockAuthority: Uint8Array }) {
+    this.tag = 11;
+    this.lockAuthority = obj.lockAuthority;
+  }
+  serialize(): Uint8Array {
+    return serialize(setLockAuthorityInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    nftOwner: PublicKey,
+    nftAccount: PublicKey,
+    nftRecord: PublicKey,
+    nameAccount: PublicKey,
+    lockRecord: PublicKey,
+    centralState: PublicKey,
+    feePayer: PublicKey,
+    splTokenProgram: PublicKey,
+    systemProgram: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: nftOwner,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftAccount,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nftRecord,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nameAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: lockRecord,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: splTokenProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class setLockInstruction {
+  tag: number;
+  locked: boolean;
+  static schema = {
+    struct: {
+      tag: "u8",
+      locked: "bool",
+    },
+  };
+  constructor(obj: { locked: boolean }) {
+    this.tag = 12;
+    this.locked = obj.locked;
+  }
+  serialize(): Uint8Array {
+    return serialize(setLockInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    lockAuthority: PublicKey,
+    lockRecord: PublicKey,
+    nftRecord: PublicKey,
+    nftAccount: PublicKey,
+    mint: PublicKey,
+    nftEdition: PublicKey,
+    centralState: PublicKey,
+    splTokenProgram: PublicKey,
+    metadataProgram: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: lockAuthority,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: lockRecord,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftRecord,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftAccount,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: mint,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftEdition,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: splTokenProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: metadataProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class manageDataInstruction {
+  tag: number;
+  space: number | null;
+  clears: { offset: number; len: number }[];
+  writes: { offset: number; data: number[] }[];
+  static schema = {
+    struct: {
+      tag: "u8",
+      space: { option: "u32" },
+      clears: { array: { type: { struct: { offset: "u32", len: "u32" } } } },
+      writes: {
+        array: {
+          type: { struct: { offset: "u32", data: { array: { type: "u8" } } } },
+        },
+      },
+    },
+  };
+  constructor(obj: {
+    space: number | null;
+    clears: { offset: number; len: number }[];
+    writes: { offset: number; data: number[] }[];
+  }) {
+    this.tag = 13;
+    this.space = obj.space;
+    this.clears = obj.clears;
+    this.writes = obj.writes;
+  }
+  serialize(): Uint8Array {
+    return serialize(manageDataInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    nftOwner: PublicKey,
+    nftAccount: PublicKey,
+    nftRecord: PublicKey,
+    nameAccount: PublicKey,
+    splTokenProgram: PublicKey,
+    splNameServiceProgram: PublicKey,
+    feePayer: PublicKey,
+    systemProgram: PublicKey,
+    nameClass?: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: nftOwner,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftRecord,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nameAccount,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: splTokenProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: splNameServiceProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    if (!!nameClass) {
+      keys.push({
+        pubkey: nameClass,
+        isSigner: true,
+        isWritable: false,
+      });
+    }
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class executeInstruction {
+  tag: number;
+  data: number[];
+  static schema = {
+    struct: {
+      tag: "u8",
+      data: { array: { type: "u8" } },
+    },
+  };
+  constructor(obj: { data: number[] }) {
+    this.tag = 14;
+    this.data = obj.data;
+  }
+  serialize(): Uint8Array {
+    return serialize(executeInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    nftOwner: PublicKey,
+    nftAccount: PublicKey,
+    nftRecord: PublicKey,
+    centralState: PublicKey,
+    targetProgram: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: nftOwner,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftRecord,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: targetProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class syncOwnerInstruction {
+  tag: number;
+  static schema = {
+    struct: {
+      tag: "u8",
+    },
+  };
+  constructor() {
+    this.tag = 15;
+  }
+  serialize(): Uint8Array {
+    return serialize(syncOwnerInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    nftRecord: PublicKey,
+    nftAccount: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: nftRecord,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nftAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class createMintAndNftInstruction {
+  tag: number;
+  name: string;
+  uri: string;
+  creatorShare: number;
+  soulbound: boolean;
+  wipeData: boolean;
+  static schema = {
+    struct: {
+      tag: "u8",
+      name: "string",
+      uri: "string",
+      creatorShare: "u8",
+      soulbound: "bool",
+      wipeData: "bool",
+    },
+  };
+  constructor(obj: {
+    name: string;
+    uri: string;
+    creatorShare: number;
+    soulbound: boolean;
+    wipeData: boolean;
+  }) {
+    this.tag = 16;
+    this.name = obj.name;
+    this.uri = obj.uri;
+    this.creatorShare = obj.creatorShare;
+    this.soulbound = obj.soulbound;
+    this.wipeData = obj.wipeData;
+  }
+  serialize(): Uint8Array {
+    return serialize(createMintAndNftInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    mint: PublicKey,
+    nftDestination: PublicKey,
+    nameAccount: PublicKey,
+    nftRecord: PublicKey,
+    nameOwner: PublicKey,
+    metadataAccount: PublicKey,
+    editionAccount: PublicKey,
+    collectionMetadata: PublicKey,
+    collectionMint: PublicKey,
+    centralState: PublicKey,
+    feePayer: PublicKey,
+    splTokenProgram: PublicKey,
+    metadataProgram: PublicKey,
+    systemProgram: PublicKey,
+    splNameServiceProgram: PublicKey,
+    rentAccount: PublicKey,
+    nftEdition: PublicKey,
+    nftEscrow: PublicKey,
+    ataProgram: PublicKey,
+    instructionsSysvar: PublicKey,
+    metadataSigner: PublicKey,
+    feeSource: PublicKey,
+    feeVault: PublicKey,
+    referrer: PublicKey,
+    tldRecord?: PublicKey,
+    nameClass?: PublicKey,
+    parentName?: PublicKey,
+    parentNftRecord?: PublicKey,
+    parentNftAccount?: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: mint,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nftDestination,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nameAccount,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nftRecord,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nameOwner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: metadataAccount,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: editionAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: collectionMetadata,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: collectionMint,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: splTokenProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: metadataProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: splNameServiceProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: rentAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftEdition,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: nftEscrow,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: ataProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: instructionsSysvar,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: metadataSigner,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feeSource,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: feeVault,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: referrer,
+      isSigner: false,
+      isWritable: true,
+    });
+    if (
+      !!tldRecord ||
+      !!nameClass ||
+      !!parentName ||
+      !!parentNftRecord ||
+      !!parentNftAccount
+    ) {
+      keys.push({
+        pubkey: tldRecord ?? programId,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (
+      !!nameClass ||
+      !!parentName ||
+      !!parentNftRecord ||
+      !!parentNftAccount
+    ) {
+      keys.push({
+        pubkey: nameClass ?? programId,
+        isSigner: !!nameClass,
+        isWritable: false,
+      });
+    }
+    if (!!parentName || !!parentNftRecord || !!parentNftAccount) {
+      keys.push({
+        pubkey: parentName ?? programId,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!parentNftRecord || !!parentNftAccount) {
+      keys.push({
+        pubkey: parentNftRecord ?? programId,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!parentNftAccount) {
+      keys.push({
+        pubkey: parentNftAccount,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class getEffectiveOwnerInstruction {
+  tag: number;
+  static schema = {
+    struct: {
+      tag: "u8",
+    },
+  };
+  constructor() {
+    this.tag = 17;
+  }
+  serialize(): Uint8Array {
+    return serialize(getEffectiveOwnerInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    nameAccount: PublicKey,
+    nftRecord: PublicKey,
+    nftAccount?: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: nameAccount,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: nftRecord,
+      isSigner: false,
+      isWritable: false,
+    });
+    if (!!nftAccount) {
+      keys.push({
+        pubkey: nftAccount,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
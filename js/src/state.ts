@@ -4,6 +4,7 @@ import { Buffer } from "buffer";
 
 export const MINT_PREFIX = Buffer.from("tokenized_name");
 export const COLLECTION_PREFIX = Buffer.from("collection");
+export const FEE_VAULT_PREFIX = Buffer.from("fee_vault");
 
 export const METADATA_SIGNER = new PublicKey(
   "Es33LnWSTZ9GbW6yBaRkSLUaFibVd7iS54e4AvBg76LX"
@@ -14,6 +15,16 @@ export enum Tag {
   CentralState = 1,
   ActiveRecord = 2,
   InactiveRecord = 3,
+  TldRecord = 4,
+  LockRecord = 5,
+}
+
+// Nonces of the accounts of the NFT of a domain
+export interface NftRecordNonces {
+  mint: number;
+  metadata: number;
+  edition: number;
+  escrow: number;
 }
 
 export class NftRecord {
@@ -22,8 +33,12 @@ export class NftRecord {
   nameAccount: PublicKey;
   owner: PublicKey;
   nftMint: PublicKey;
+  class: PublicKey;
+  soulbound: boolean;
+  // Undefined for legacy records
+  nonces?: NftRecordNonces;
 
-  static schema = {
+  static legacySchema = {
     struct: {
       tag: "u8",
       nonce: "u8",
@@ -33,21 +48,44 @@ export class NftRecord {
     },
   };
 
+  static schema = {
+    struct: {
+      ...NftRecord.legacySchema.struct,
+      class: { array: { type: "u8", len: 32 } },
+      soulbound: "bool",
+      nonces: {
+        struct: { mint: "u8", metadata: "u8", edition: "u8", escrow: "u8" },
+      },
+    },
+  };
+
+  // Records created before the class, soulbound and nonces fields were added
+  static LEGACY_LEN = 1 + 1 + 32 + 32 + 32;
+
   constructor(obj: {
     tag: number;
     nonce: number;
     nameAccount: Uint8Array;
     owner: Uint8Array;
     nftMint: Uint8Array;
+    class?: Uint8Array;
+    soulbound?: boolean;
+    nonces?: NftRecordNonces;
   }) {
     this.tag = obj.tag as Tag;
     this.nonce = obj.nonce;
     this.nameAccount = new PublicKey(obj.nameAccount);
     this.owner = new PublicKey(obj.owner);
     this.nftMint = new PublicKey(obj.nftMint);
+    this.class = obj.class ? new PublicKey(obj.class) : PublicKey.default;
+    this.soulbound = !!obj.soulbound;
+    this.nonces = obj.nonces;
   }
 
   static deserialize(data: Buffer): NftRecord {
+    if (data.length <= this.LEGACY_LEN) {
+      return new NftRecord(deserialize(this.legacySchema, data) as any);
+    }
     return new NftRecord(deserialize(this.schema, data) as any);
   }
 
@@ -56,7 +56,7 @@ export function deployProgram(
     });
   }
   if (testBpf) {
-    execSync("cargo test-bpf --features devnet", {
+    execSync("cargo test-bpf", {
       cwd: programDirectory,
     });
   }
@@ -0,0 +1,182 @@
+//! Cluster specific configuration
+//!
+//! The program selects its configuration at runtime from its own program ID, which keeps the
+//! instruction layout identical on every cluster.
+
+use solana_program::{msg, program_error::ProgramError, pubkey, pubkey::Pubkey};
+
+use crate::state::{FeeVaultNonces, FEE_VAULT_PREFIX, METADATA_SIGNER, ROOT_DOMAIN_ACCOUNT};
+
+/// Keys of a name tokenizer deployment
+pub struct Config {
+    /// The name tokenizer program ID
+    pub program_id: Pubkey,
+
+    /// The central state of the program, derived from the program ID
+    pub central_state: Pubkey,
+
+    /// The nonce of the central state
+    pub central_state_nonce: u8,
+
+    /// The root domain of `.sol` domains
+    pub root_domain: Pubkey,
+
+    /// The signer of custom metadata URIs (`None` if URIs are not verified)
+    pub metadata_signer: Option<Pubkey>,
+
+    /// The mint of the `.sol` collection
+    pub collection_mint: Pubkey,
+
+    /// The nonce of the `.sol` collection mint
+    pub collection_nonce: u8,
+
+    /// The nonce of the Metaplex metadata of the `.sol` collection
+    pub collection_metadata_nonce: u8,
+
+    /// The nonce of the Metaplex master edition of the `.sol` collection
+    pub collection_edition_nonce: u8,
+}
+
+/// Mainnet deployment
+pub const MAINNET: Config = Config {
+    program_id: crate::ID,
+    central_state: pubkey!("DL834WsTySeC2mJ5Wu9Unn2rYb6Abrot9P1b1Gq1XUVX"),
+    central_state_nonce: 255,
+    root_domain: ROOT_DOMAIN_ACCOUNT,
+    metadata_signer: Some(METADATA_SIGNER),
+    collection_mint: pubkey!("E5ZnBpH9DYcxRkumKdS4ayJ3Ftb6o3E8wSbXw4N92GWg"),
+    collection_nonce: 254,
+    collection_metadata_nonce: 255,
+    collection_edition_nonce: 255,
+};
+
+/// Devnet deployment
+pub const DEVNET: Config = Config {
+    program_id: pubkey!("45gRSRZmK6NDEJrCZ72MMddjA1ozufq9YQpm41poPXCE"),
+    central_state: pubkey!("C6VyqT5wTpQ1xXpKZDv3nAmWs7diQDGtwqKp4CD6AVW1"),
+    central_state_nonce: 254,
+    root_domain: ROOT_DOMAIN_ACCOUNT,
+    metadata_signer: None,
+    collection_mint: pubkey!("GSWJp1tvsPSxd8ZGoxGkkJ2kdJospSXceAZ3omYNz1Tw"),
+    collection_nonce: 254,
+    collection_metadata_nonce: 253,
+    collection_edition_nonce: 254,
+};
+
+/// Local validator and tests
+pub const LOCALNET: Config = Config {
+    program_id: pubkey!("9RkxXEbo1cLrrAxQp6QdEzRQRdqidfQkRDZE3oqMNN4d"),
+    central_state: pubkey!("8Cet6ekaCkdK1WsZ7ehLPMLAkAtwHDNAZmZKPYJre9kV"),
+    central_state_nonce: 255,
+    root_domain: ROOT_DOMAIN_ACCOUNT,
+    metadata_signer: None,
+    collection_mint: pubkey!("12LDZExdr5mgEpYUheZB8oowtgu1PHt2jgtndcTRJL3B"),
+    collection_nonce: 254,
+    collection_metadata_nonce: 250,
+    collection_edition_nonce: 255,
+};
+
+/// Local tests verifying custom metadata URIs, the metadata signer derives from a public seed
+pub const LOCALNET_ATTESTED: Config = Config {
+    program_id: pubkey!("6nCkjjfoNv7XyL6V14HCedDvxAjxsYa3dptj9rqLxMKA"),
+    central_state: pubkey!("F4LtNVVwGQ2S9bJpkRgBDE8q4V7S6EvNvHti1Q6z4FeY"),
+    central_state_nonce: 254,
+    root_domain: ROOT_DOMAIN_ACCOUNT,
+    metadata_signer: Some(pubkey!("EqCk8i4vzc2aLHW2KvbsVSfiWpXh55NbA217FaE4oEEP")),
+    collection_mint: pubkey!("GfJbofb7ioqgW8AHyvuhpV8j4sj9sB85k8RKGE2LQAKs"),
+    collection_nonce: 255,
+    collection_metadata_nonce: 254,
+    collection_edition_nonce: 254,
+};
+
+impl Config {
+    pub fn from_program_id(program_id: &Pubkey) -> Result<&'static Config, ProgramError> {
+        [&MAINNET, &DEVNET, &LOCALNET, &LOCALNET_ATTESTED]
+            .iter()
+            .copied()
+            .find(|c| &c.program_id == program_id)
+            .ok_or_else(|| {
+                msg!("+ No configuration for program {}", program_id);
+                ProgramError::IncorrectProgramId
+            })
+    }
+
+    /// The central state token account holding the NFT of `nft_mint` while the domain is redeemed
+    pub fn find_nft_escrow_key(&self, nft_mint: &Pubkey) -> (Pubkey, u8) {
+        find_associated_token_key(&self.central_state, nft_mint)
+    }
+
+    /// The NFT escrow of `nft_mint`, derived from its stored nonce
+    pub fn nft_escrow_key(&self, nft_mint: &Pubkey, nonce: u8) -> Result<Pubkey, ProgramError> {
+        associated_token_key(&self.central_state, nft_mint, nonce)
+    }
+
+    /// The authority of the token vault collecting the protocol fees of `fee_mint`, it is
+    /// distinct from the central state so that fee vaults never collide with the NFT escrows
+    pub fn find_fee_vault_authority(&self, fee_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[FEE_VAULT_PREFIX, &fee_mint.to_bytes()], &self.program_id)
+    }
+
+    /// The vault collecting the protocol fees of `fee_mint`, the central state itself for SOL
+    pub fn find_fee_vault_key(&self, fee_mint: &Pubkey) -> Pubkey {
+        if fee_mint == &Pubkey::default() {
+            self.central_state
+        } else {
+            let (authority, _) = self.find_fee_vault_authority(fee_mint);
+            find_associated_token_key(&authority, fee_mint).0
+        }
+    }
+
+    /// The nonces of the token vault collecting the protocol fees of `fee_mint`
+    pub fn find_fee_vault_nonces(&self, fee_mint: &Pubkey) -> FeeVaultNonces {
+        if fee_mint == &Pubkey::default() {
+            return FeeVaultNonces::default();
+        }
+        let (authority, authority_nonce) = self.find_fee_vault_authority(fee_mint);
+        let (_, vault_nonce) = find_associated_token_key(&authority, fee_mint);
+        FeeVaultNonces {
+            authority: authority_nonce,
+            vault: vault_nonce,
+        }
+    }
+
+    /// The vault collecting the protocol fees of `fee_mint`, derived from its stored nonces
+    pub fn fee_vault_key(
+        &self,
+        fee_mint: &Pubkey,
+        nonces: &FeeVaultNonces,
+    ) -> Result<Pubkey, ProgramError> {
+        if fee_mint == &Pubkey::default() {
+            return Ok(self.central_state);
+        }
+        let authority = Pubkey::create_program_address(
+            &[FEE_VAULT_PREFIX, &fee_mint.to_bytes(), &[nonces.authority]],
+            &self.program_id,
+        )?;
+        associated_token_key(&authority, fee_mint, nonces.vault)
+    }
+}
+
+fn find_associated_token_key(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            &owner.to_bytes(),
+            &spl_token::ID.to_bytes(),
+            &mint.to_bytes(),
+        ],
+        &spl_associated_token_account::ID,
+    )
+}
+
+fn associated_token_key(owner: &Pubkey, mint: &Pubkey, nonce: u8) -> Result<Pubkey, ProgramError> {
+    let key = Pubkey::create_program_address(
+        &[
+            &owner.to_bytes(),
+            &spl_token::ID.to_bytes(),
+            &mint.to_bytes(),
+            &[nonce],
+        ],
+        &spl_associated_token_account::ID,
+    )?;
+    Ok(key)
+}
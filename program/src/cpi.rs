@@ -1,14 +1,26 @@
+#[cfg(feature = "cpi")]
+mod client;
+#[cfg(feature = "cpi")]
+pub use client::*;
+
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke_signed,
-    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, rent::Rent,
-    system_instruction::create_account, sysvar::Sysvar,
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::{allocate, assign, create_account, transfer},
+    sysvar::Sysvar,
 };
 
 #[allow(missing_docs)]
 pub struct Cpi {}
 
 impl Cpi {
-    #[allow(missing_docs)]
+    /// Creates a PDA owned by `program_id`. Lamports sent to the address beforehand would make
+    /// `create_account` fail, so a funded account is topped up, allocated and assigned instead.
     pub fn create_account<'a>(
         program_id: &Pubkey,
         system_program: &AccountInfo<'a>,
@@ -17,25 +29,72 @@ impl Cpi {
         signer_seeds: &[&[u8]],
         space: usize,
     ) -> ProgramResult {
-        let create_state_instruction = create_account(
-            fee_payer.key,
-            account_to_create.key,
-            Rent::get()?.minimum_balance(space),
-            space as u64,
-            program_id,
-        );
+        let rent = Rent::get()?.minimum_balance(space);
+        let current_lamports = account_to_create.lamports();
+
+        if current_lamports == 0 {
+            let create_state_instruction = create_account(
+                fee_payer.key,
+                account_to_create.key,
+                rent,
+                space as u64,
+                program_id,
+            );
+
+            return invoke_signed(
+                &create_state_instruction,
+                &[
+                    system_program.clone(),
+                    fee_payer.clone(),
+                    account_to_create.clone(),
+                ],
+                &[signer_seeds],
+            );
+        }
 
+        verbose_msg!("+ Account already funded, allocating it");
+        let lamports = rent.saturating_sub(current_lamports);
+        if lamports > 0 {
+            invoke(
+                &transfer(fee_payer.key, account_to_create.key, lamports),
+                &[
+                    system_program.clone(),
+                    fee_payer.clone(),
+                    account_to_create.clone(),
+                ],
+            )?;
+        }
         invoke_signed(
-            &create_state_instruction,
-            &[
-                system_program.clone(),
-                fee_payer.clone(),
-                account_to_create.clone(),
-            ],
+            &allocate(account_to_create.key, space as u64),
+            &[system_program.clone(), account_to_create.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &assign(account_to_create.key, program_id),
+            &[system_program.clone(), account_to_create.clone()],
             &[signer_seeds],
         )
     }
 
+    /// Resizes a program owned account, the fee payer tops up the rent if needed
+    pub fn resize_account<'a>(
+        system_program: &AccountInfo<'a>,
+        fee_payer: &AccountInfo<'a>,
+        account: &AccountInfo<'a>,
+        space: usize,
+    ) -> ProgramResult {
+        let lamports = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(account.lamports());
+        if lamports > 0 {
+            invoke(
+                &transfer(fee_payer.key, account.key, lamports),
+                &[system_program.clone(), fee_payer.clone(), account.clone()],
+            )?;
+        }
+        account.realloc(space, false)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn allocate_and_create_token_account<'a>(
         token_account_owner: &Pubkey,
@@ -47,24 +106,14 @@ impl Cpi {
         rent_account: &AccountInfo<'a>,
         system_program_info: &AccountInfo<'a>,
     ) -> Result<(), ProgramError> {
-        msg!("Initializing token account");
-        let size = spl_token::state::Account::LEN;
-        let required_lamports = Rent::get()?.minimum_balance(size);
-        let ix_allocate = create_account(
-            payer_info.key,
-            token_account.key,
-            required_lamports,
-            size as u64,
+        verbose_msg!("Initializing token account");
+        Self::create_account(
             &spl_token::ID,
-        );
-        invoke_signed(
-            &ix_allocate,
-            &[
-                system_program_info.clone(),
-                payer_info.clone(),
-                token_account.clone(),
-            ],
-            &[signer_seeds],
+            system_program_info,
+            payer_info,
+            token_account,
+            signer_seeds,
+            spl_token::state::Account::LEN,
         )?;
         let ix_initialize = spl_token::instruction::initialize_account2(
             &spl_token::ID,
@@ -0,0 +1,403 @@
+//! Typed CPI wrappers of the tokenizer instructions, for programs composing with the tokenizer
+//!
+//! Each instruction has an `invoke` wrapper and a `_signed` variant taking the seeds of the PDAs
+//! of the calling program. The instruction is built with the key of `tokenizer_program`.
+
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    msg,
+    program::{get_return_data, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::instruction::{self, get_effective_owner::EffectiveOwner};
+
+macro_rules! cpi_wrapper {
+    ($module:ident, $signed:ident, [$($field:ident),*], [$($optional:ident),*]) => {
+        #[doc = concat!("Invokes `", stringify!($module), "`")]
+        pub fn $module<'a>(
+            tokenizer_program: &AccountInfo<'a>,
+            accounts: &instruction::$module::Accounts<AccountInfo<'a>>,
+            params: instruction::$module::Params,
+        ) -> ProgramResult {
+            $signed(tokenizer_program, accounts, params, &[])
+        }
+
+        #[doc = concat!("Invokes `", stringify!($module), "` with the seeds of the PDAs of the caller")]
+        pub fn $signed<'a>(
+            tokenizer_program: &AccountInfo<'a>,
+            accounts: &instruction::$module::Accounts<AccountInfo<'a>>,
+            params: instruction::$module::Params,
+            signer_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            let keys = instruction::$module::Accounts {
+                $($field: accounts.$field.key,)*
+                $($optional: accounts.$optional.map(|a| a.key),)*
+            };
+            let ix = instruction::$module(*tokenizer_program.key, keys, params);
+
+            let mut account_infos = vec![tokenizer_program.clone() $(, accounts.$field.clone())*];
+            let optional: &[Option<&AccountInfo<'a>>] = &[$(accounts.$optional),*];
+            account_infos.extend(optional.iter().flatten().map(|a| (*a).clone()));
+            invoke_signed(&ix, &account_infos, signer_seeds)
+        }
+    };
+}
+
+cpi_wrapper!(
+    create_mint,
+    create_mint_signed,
+    [
+        mint,
+        name_account,
+        central_state,
+        spl_token_program,
+        system_program,
+        rent_account,
+        fee_payer
+    ],
+    []
+);
+cpi_wrapper!(
+    create_collection,
+    create_collection_signed,
+    [
+        collection_mint,
+        edition,
+        metadata_account,
+        central_state,
+        central_state_nft_ata,
+        fee_payer,
+        spl_token_program,
+        metadata_program,
+        system_program,
+        spl_name_service_program,
+        ata_program,
+        rent_account
+    ],
+    [tld_record]
+);
+cpi_wrapper!(
+    create_nft,
+    create_nft_signed,
+    [
+        mint,
+        nft_destination,
+        name_account,
+        nft_record,
+        name_owner,
+        metadata_account,
+        edition_account,
+        collection_metadata,
+        collection_mint,
+        central_state,
+        fee_payer,
+        spl_token_program,
+        metadata_program,
+        system_program,
+        spl_name_service_program,
+        rent_account,
+        nft_edition,
+        nft_escrow,
+        ata_program,
+        instructions_sysvar,
+        metadata_signer,
+        fee_source,
+        fee_vault,
+        referrer
+    ],
+    [
+        tld_record,
+        name_class,
+        parent_name,
+        parent_nft_record,
+        parent_nft_account
+    ]
+);
+cpi_wrapper!(
+    create_mint_and_nft,
+    create_mint_and_nft_signed,
+    [
+        mint,
+        nft_destination,
+        name_account,
+        nft_record,
+        name_owner,
+        metadata_account,
+        edition_account,
+        collection_metadata,
+        collection_mint,
+        central_state,
+        fee_payer,
+        spl_token_program,
+        metadata_program,
+        system_program,
+        spl_name_service_program,
+        rent_account,
+        nft_edition,
+        nft_escrow,
+        ata_program,
+        instructions_sysvar,
+        metadata_signer,
+        fee_source,
+        fee_vault,
+        referrer
+    ],
+    [
+        tld_record,
+        name_class,
+        parent_name,
+        parent_nft_record,
+        parent_nft_account
+    ]
+);
+cpi_wrapper!(
+    redeem_nft,
+    redeem_nft_signed,
+    [
+        mint,
+        nft_source,
+        nft_owner,
+        nft_record,
+        name_account,
+        spl_token_program,
+        spl_name_service_program,
+        nft_escrow,
+        central_state,
+        system_program,
+        fee_source,
+        fee_vault,
+        referrer,
+        nft_edition,
+        metadata_program
+    ],
+    [name_class]
+);
+cpi_wrapper!(
+    withdraw_tokens,
+    withdraw_tokens_signed,
+    [
+        nft,
+        nft_owner,
+        nft_record,
+        token_destination,
+        token_source,
+        spl_token_program,
+        system_program
+    ],
+    []
+);
+cpi_wrapper!(
+    edit_data,
+    edit_data_signed,
+    [
+        nft_owner,
+        nft_account,
+        nft_record,
+        name_account,
+        spl_token_program,
+        spl_name_service_program
+    ],
+    [name_class]
+);
+cpi_wrapper!(
+    manage_data,
+    manage_data_signed,
+    [
+        nft_owner,
+        nft_account,
+        nft_record,
+        name_account,
+        spl_token_program,
+        spl_name_service_program,
+        fee_payer,
+        system_program
+    ],
+    [name_class]
+);
+cpi_wrapper!(
+    unverify_nft,
+    unverify_nft_signed,
+    [
+        metadata_account,
+        edition_account,
+        collection_metadata,
+        collection_mint,
+        central_state,
+        fee_payer,
+        metadata_program,
+        system_program,
+        rent_account,
+        metadata_signer
+    ],
+    [tld_record]
+);
+cpi_wrapper!(
+    register_tld,
+    register_tld_signed,
+    [
+        tld_record,
+        tld,
+        program_data,
+        admin,
+        fee_payer,
+        system_program
+    ],
+    []
+);
+cpi_wrapper!(
+    update_central_state,
+    update_central_state_signed,
+    [
+        central_state,
+        program_data,
+        admin,
+        fee_payer,
+        system_program
+    ],
+    []
+);
+cpi_wrapper!(
+    edit_creators,
+    edit_creators_signed,
+    [
+        nft_owner,
+        nft_account,
+        nft_record,
+        name_account,
+        metadata_account,
+        central_state,
+        metadata_program
+    ],
+    []
+);
+cpi_wrapper!(
+    withdraw_fees,
+    withdraw_fees_signed,
+    [
+        central_state,
+        fee_vault,
+        destination,
+        program_data,
+        admin,
+        spl_token_program
+    ],
+    [fee_vault_authority]
+);
+cpi_wrapper!(
+    set_lock_authority,
+    set_lock_authority_signed,
+    [
+        nft_owner,
+        nft_account,
+        nft_record,
+        name_account,
+        lock_record,
+        central_state,
+        fee_payer,
+        spl_token_program,
+        system_program
+    ],
+    []
+);
+cpi_wrapper!(
+    set_lock,
+    set_lock_signed,
+    [
+        lock_authority,
+        lock_record,
+        nft_record,
+        nft_account,
+        mint,
+        nft_edition,
+        central_state,
+        spl_token_program,
+        metadata_program
+    ],
+    []
+);
+cpi_wrapper!(sync_owner, sync_owner_signed, [nft_record, nft_account], []);
+cpi_wrapper!(
+    get_effective_owner,
+    get_effective_owner_signed,
+    [name_account, nft_record],
+    [nft_account]
+);
+
+/// Reads the `EffectiveOwner` returned by the last `get_effective_owner` invocation
+pub fn read_effective_owner(tokenizer_program: &Pubkey) -> Result<EffectiveOwner, ProgramError> {
+    match get_return_data() {
+        Some((program_id, data)) if &program_id == tokenizer_program => {
+            EffectiveOwner::try_from_slice(&data).map_err(|_| {
+                msg!("+ Invalid effective owner return data");
+                ProgramError::InvalidAccountData
+            })
+        }
+        _ => {
+            msg!("+ Missing effective owner return data");
+            Err(ProgramError::InvalidArgument)
+        }
+    }
+}
+
+/// Invokes `execute`, the accounts of the instruction invoked through the `NftRecord` are given by
+/// `remaining_accounts`
+pub fn execute<'a>(
+    tokenizer_program: &AccountInfo<'a>,
+    accounts: &instruction::execute::Accounts<AccountInfo<'a>>,
+    target_data: Vec<u8>,
+    remaining_accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    execute_signed(
+        tokenizer_program,
+        accounts,
+        target_data,
+        remaining_accounts,
+        &[],
+    )
+}
+
+/// Invokes `execute` with the seeds of the PDAs of the caller
+pub fn execute_signed<'a>(
+    tokenizer_program: &AccountInfo<'a>,
+    accounts: &instruction::execute::Accounts<AccountInfo<'a>>,
+    target_data: Vec<u8>,
+    remaining_accounts: &[AccountInfo<'a>],
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let keys = instruction::execute::Accounts {
+        nft_owner: accounts.nft_owner.key,
+        nft_account: accounts.nft_account.key,
+        nft_record: accounts.nft_record.key,
+        central_state: accounts.central_state.key,
+        target_program: accounts.target_program.key,
+    };
+    let target_ix = solana_program::instruction::Instruction {
+        program_id: *accounts.target_program.key,
+        accounts: remaining_accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: *a.key,
+                is_signer: a.is_signer,
+                is_writable: a.is_writable,
+            })
+            .collect(),
+        data: target_data,
+    };
+    let ix = instruction::execute(*tokenizer_program.key, keys, target_ix);
+
+    let mut account_infos = vec![
+        tokenizer_program.clone(),
+        accounts.nft_owner.clone(),
+        accounts.nft_account.clone(),
+        accounts.nft_record.clone(),
+        accounts.central_state.clone(),
+        accounts.target_program.clone(),
+    ];
+    account_infos.extend(remaining_accounts.iter().cloned());
+    invoke_signed(&ix, &account_infos, signer_seeds)
+}
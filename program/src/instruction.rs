@@ -1,12 +1,15 @@
+use crate::config::Config;
 pub use crate::processor::{
-    create_collection, create_mint, create_nft, edit_data, redeem_nft, unverify_nft,
+    create_collection, create_mint, create_mint_and_nft, create_nft, edit_creators, edit_data,
+    execute, get_effective_owner, manage_data, redeem_nft, register_tld, set_lock,
+    set_lock_authority, sync_owner, unverify_nft, update_central_state, withdraw_fees,
     withdraw_tokens,
 };
 use {
     bonfida_utils::InstructionsAccount,
     borsh::{BorshDeserialize, BorshSerialize},
     num_derive::FromPrimitive,
-    solana_program::{instruction::Instruction, pubkey::Pubkey},
+    solana_program::{ed25519_program, instruction::Instruction, pubkey::Pubkey},
 };
 #[allow(missing_docs)]
 #[derive(BorshDeserialize, BorshSerialize, FromPrimitive)]
@@ -39,40 +42,66 @@ pub enum ProgramInstruction {
     /// | 9     | ❌        | ❌      | The SPL name service program account                          |
     /// | 10    | ❌        | ❌      |                                                               |
     /// | 11    | ❌        | ❌      | Rent sysvar account                                           |
+    /// | 12    | ❌        | ❌      | The TLD record account (omitted for .sol)                     |
     CreateCollection,
     /// Tokenize a domain name
     ///
-    /// | Index | Writable | Signer | Description                          |
-    /// | ---------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The mint of the NFT                  |
-    /// | 1     | ✅        | ❌      | The NFT token destination            |
-    /// | 2     | ✅        | ❌      | The domain name account              |
-    /// | 3     | ✅        | ❌      | The NFT record account               |
-    /// | 4     | ✅        | ✅      | The domain name owner                |
-    /// | 5     | ✅        | ❌      | The metadata account                 |
-    /// | 6     | ❌        | ❌      | Master edition account               |
-    /// | 7     | ❌        | ❌      | Collection                           |
-    /// | 8     | ❌        | ❌      | Mint of the collection               |
-    /// | 9     | ✅        | ❌      | The central state account            |
-    /// | 10    | ✅        | ✅      | The fee payer account                |
-    /// | 11    | ❌        | ❌      | The SPL token program account        |
-    /// | 12    | ❌        | ❌      | The metadata program account         |
-    /// | 13    | ❌        | ❌      | The system program account           |
-    /// | 14    | ❌        | ❌      | The SPL name service program account |
-    /// | 15    | ❌        | ❌      | Rent sysvar account                  |
-    /// | 16    | ❌        | ✅      | The metadata signer                  |
+    /// The metadata signer is only required to sign custom URIs, an empty URI is derived on-chain.
+    /// Instead of co-signing, the metadata signer can attest a custom URI with an ed25519
+    /// signature over `create_nft::attestation_message` verified by the previous instruction.
+    ///
+    /// | Index | Writable | Signer | Description                                                                                 |
+    /// | ----------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The mint of the NFT                                                                         |
+    /// | 1     | ✅        | ❌      | The NFT token destination                                                                   |
+    /// | 2     | ✅        | ❌      | The domain name account                                                                     |
+    /// | 3     | ✅        | ❌      | The NFT record account                                                                      |
+    /// | 4     | ✅        | ✅      | The domain name owner                                                                       |
+    /// | 5     | ✅        | ❌      | The metadata account                                                                        |
+    /// | 6     | ❌        | ❌      | Master edition account                                                                      |
+    /// | 7     | ❌        | ❌      | Collection                                                                                  |
+    /// | 8     | ❌        | ❌      | Mint of the collection                                                                      |
+    /// | 9     | ✅        | ❌      | The central state account                                                                   |
+    /// | 10    | ✅        | ✅      | The fee payer account                                                                       |
+    /// | 11    | ❌        | ❌      | The SPL token program account                                                               |
+    /// | 12    | ❌        | ❌      | The metadata program account                                                                |
+    /// | 13    | ❌        | ❌      | The system program account                                                                  |
+    /// | 14    | ❌        | ❌      | The SPL name service program account                                                        |
+    /// | 15    | ❌        | ❌      | Rent sysvar account                                                                         |
+    /// | 16    | ✅        | ❌      | The master edition account of the NFT                                                       |
+    /// | 17    | ✅        | ❌      | The central state escrow of the NFT                                                         |
+    /// | 18    | ❌        | ❌      | The associated token account program account                                                |
+    /// | 19    | ❌        | ❌      | The instructions sysvar account                                                             |
+    /// | 20    | ❌        | ❌      | The metadata signer, co-signing custom URIs that are not attested                           |
+    /// | 21    | ✅        | ❌      | The account paying the protocol fee (the fee payer for SOL fees, or its token account)      |
+    /// | 22    | ✅        | ❌      | The fee vault of the central state                                                          |
+    /// | 23    | ✅        | ❌      | The referrer receiving a share of the protocol fee (the fee vault if there is none)         |
+    /// | 24    | ❌        | ❌      | The TLD record account (omitted for .sol)                                                   |
+    /// | 25    | ❌        | ✅      | The class of the domain name (omitted if the domain has no class)                           |
+    /// | 26    | ❌        | ❌      | The parent domain when a subdomain is tokenized by the owner of its parent                  |
+    /// | 27    | ❌        | ❌      | The NFT record of the parent domain if it is tokenized                                      |
+    /// | 28    | ❌        | ❌      | The token account of the name owner holding the NFT of the parent domain if it is tokenized |
     CreateNft,
     /// Redeem a tokenized domain name
     ///
-    /// | Index | Writable | Signer | Description                               |
-    /// | --------------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The mint of the NFT                       |
-    /// | 1     | ✅        | ❌      | The current token account holding the NFT |
-    /// | 2     | ✅        | ✅      | The NFT owner account                     |
-    /// | 3     | ✅        | ❌      | The NFT record account                    |
-    /// | 4     | ✅        | ❌      | The domain name account                   |
-    /// | 5     | ❌        | ❌      | The SPL token program account             |
-    /// | 6     | ❌        | ❌      | The SPL name service program account      |
+    /// | Index | Writable | Signer | Description                                                                            |
+    /// | ------------------------------------------------------------------------------------------------------------------ |
+    /// | 0     | ✅        | ❌      | The mint of the NFT                                                                    |
+    /// | 1     | ✅        | ❌      | The current token account holding the NFT                                              |
+    /// | 2     | ✅        | ✅      | The NFT owner account                                                                  |
+    /// | 3     | ✅        | ❌      | The NFT record account                                                                 |
+    /// | 4     | ✅        | ❌      | The domain name account                                                                |
+    /// | 5     | ❌        | ❌      | The SPL token program account                                                          |
+    /// | 6     | ❌        | ❌      | The SPL name service program account                                                   |
+    /// | 7     | ✅        | ❌      | The central state escrow of the NFT                                                    |
+    /// | 8     | ✅        | ❌      | The central state account                                                              |
+    /// | 9     | ❌        | ❌      | The system program account                                                             |
+    /// | 10    | ✅        | ❌      | The account paying the protocol fee (the NFT owner for SOL fees, or its token account) |
+    /// | 11    | ✅        | ❌      | The fee vault of the central state                                                     |
+    /// | 12    | ✅        | ❌      | The referrer receiving a share of the protocol fee (the fee vault if there is none)    |
+    /// | 13    | ❌        | ❌      | The master edition account of the NFT                                                  |
+    /// | 14    | ❌        | ❌      | The metadata program account                                                           |
+    /// | 15    | ❌        | ✅      | The class of the domain name (omitted if the domain has no class)                      |
     RedeemNft,
     /// Withdraw funds that have been sent to the escrow
     /// while the domain was tokenized
@@ -89,79 +118,454 @@ pub enum ProgramInstruction {
     WithdrawTokens,
     /// Edit the data registry of a tokenized domain name
     ///
-    /// | Index | Writable | Signer | Description                          |
-    /// | ---------------------------------------------------------------- |
-    /// | 0     | ❌        | ✅      | The NFT owner account                |
-    /// | 1     | ❌        | ❌      | The NFT account                      |
-    /// | 2     | ❌        | ❌      | The NFT record account               |
-    /// | 3     | ✅        | ❌      | The domain name account              |
-    /// | 4     | ❌        | ❌      | The SPL token program account        |
-    /// | 5     | ❌        | ❌      | The SPL name service program account |
+    /// | Index | Writable | Signer | Description                                                       |
+    /// | --------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The NFT owner account                                             |
+    /// | 1     | ❌        | ❌      | The NFT account                                                   |
+    /// | 2     | ❌        | ❌      | The NFT record account                                            |
+    /// | 3     | ✅        | ❌      | The domain name account                                           |
+    /// | 4     | ❌        | ❌      | The SPL token program account                                     |
+    /// | 5     | ❌        | ❌      | The SPL name service program account                              |
+    /// | 6     | ❌        | ✅      | The class of the domain name (omitted if the domain has no class) |
     EditData,
     /// Unverify an NFT
     ///
+    /// | Index | Writable | Signer | Description                                              |
+    /// | ------------------------------------------------------------------------------------ |
+    /// | 0     | ✅        | ❌      | The metadata account                                     |
+    /// | 1     | ❌        | ❌      | Master edition account                                   |
+    /// | 2     | ❌        | ❌      | Collection                                               |
+    /// | 3     | ❌        | ❌      | Mint of the collection                                   |
+    /// | 4     | ✅        | ❌      | The central state account                                |
+    /// | 5     | ✅        | ✅      | The fee payer account                                    |
+    /// | 6     | ❌        | ❌      | The metadata program account                             |
+    /// | 7     | ❌        | ❌      | The system program account                               |
+    /// | 8     | ❌        | ❌      | Rent sysvar account                                      |
+    /// | 9     | ❌        | ✅      | The metadata signer (any signer on clusters without one) |
+    /// | 10    | ❌        | ❌      | The TLD record account (omitted for .sol)                |
+    UnverifyNft,
+    /// Register a new TLD that can be tokenized
+    ///
+    /// | Index | Writable | Signer | Description                               |
+    /// | --------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The TLD record account                    |
+    /// | 1     | ❌        | ❌      | The parent name account of the TLD        |
+    /// | 2     | ❌        | ❌      | The program data account of the tokenizer |
+    /// | 3     | ❌        | ✅      | The upgrade authority of the tokenizer    |
+    /// | 4     | ✅        | ✅      | The fee payer account                     |
+    /// | 5     | ❌        | ❌      | The system program account                |
+    RegisterTld,
+    /// Create or update the central state configuration
+    ///
+    /// | Index | Writable | Signer | Description                               |
+    /// | --------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                 |
+    /// | 1     | ❌        | ❌      | The program data account of the tokenizer |
+    /// | 2     | ❌        | ✅      | The upgrade authority of the tokenizer    |
+    /// | 3     | ✅        | ✅      | The fee payer account                     |
+    /// | 4     | ❌        | ❌      | The system program account                |
+    UpdateCentralState,
+    /// Edit the royalty split of a tokenized domain name
+    ///
     /// | Index | Writable | Signer | Description                  |
     /// | -------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The metadata account         |
-    /// | 1     | ❌        | ❌      | Master edition account       |
-    /// | 2     | ❌        | ❌      | Collection                   |
-    /// | 3     | ❌        | ❌      | Mint of the collection       |
-    /// | 4     | ✅        | ❌      | The central state account    |
-    /// | 5     | ✅        | ✅      | The fee payer account        |
+    /// | 0     | ❌        | ✅      | The NFT owner account        |
+    /// | 1     | ❌        | ❌      | The NFT account              |
+    /// | 2     | ❌        | ❌      | The NFT record account       |
+    /// | 3     | ❌        | ❌      | The domain name account      |
+    /// | 4     | ✅        | ❌      | The metadata account         |
+    /// | 5     | ❌        | ❌      | The central state account    |
     /// | 6     | ❌        | ❌      | The metadata program account |
-    /// | 7     | ❌        | ❌      | The system program account   |
-    /// | 8     | ❌        | ❌      | Rent sysvar account          |
-    /// | 9     | ❌        | ✅      | The metadata signer          |
-    UnverifyNft,
+    EditCreators,
+    /// Withdraw the protocol fees collected in a fee vault
+    ///
+    /// | Index | Writable | Signer | Description                                          |
+    /// | -------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                            |
+    /// | 1     | ✅        | ❌      | The fee vault (the central state for SOL fees)       |
+    /// | 2     | ✅        | ❌      | The destination of the fees                          |
+    /// | 3     | ❌        | ❌      | The program data account of the tokenizer            |
+    /// | 4     | ❌        | ✅      | The upgrade authority of the tokenizer               |
+    /// | 5     | ❌        | ❌      | The SPL token program account                        |
+    /// | 6     | ❌        | ❌      | The authority of the fee vault (only for token fees) |
+    WithdrawFees,
+    /// Grant or revoke the authority allowed to lock a domain NFT in the holder's wallet
+    ///
+    /// | Index | Writable | Signer | Description                          |
+    /// | ---------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The NFT owner account                |
+    /// | 1     | ✅        | ❌      | The token account holding the NFT    |
+    /// | 2     | ❌        | ❌      | The NFT record account               |
+    /// | 3     | ❌        | ❌      | The domain name account              |
+    /// | 4     | ✅        | ❌      | The lock record of the token account |
+    /// | 5     | ❌        | ❌      | The central state account            |
+    /// | 6     | ✅        | ✅      | The fee payer account                |
+    /// | 7     | ❌        | ❌      | The SPL token program account        |
+    /// | 8     | ❌        | ❌      | The system program account           |
+    SetLockAuthority,
+    /// Lock or unlock a domain NFT in the holder's wallet
+    ///
+    /// | Index | Writable | Signer | Description                             |
+    /// | ------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The lock authority of the token account |
+    /// | 1     | ❌        | ❌      | The lock record of the token account    |
+    /// | 2     | ❌        | ❌      | The NFT record account                  |
+    /// | 3     | ✅        | ❌      | The token account holding the NFT       |
+    /// | 4     | ❌        | ❌      | The mint of the NFT                     |
+    /// | 5     | ❌        | ❌      | The master edition account of the NFT   |
+    /// | 6     | ❌        | ❌      | The central state account               |
+    /// | 7     | ❌        | ❌      | The SPL token program account           |
+    /// | 8     | ❌        | ❌      | The metadata program account            |
+    SetLock,
+    /// Resize and edit several ranges of the data registry of a tokenized domain name
+    ///
+    /// | Index | Writable | Signer | Description                                                               |
+    /// | ----------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The NFT owner account                                                     |
+    /// | 1     | ❌        | ❌      | The NFT account                                                           |
+    /// | 2     | ❌        | ❌      | The NFT record account                                                    |
+    /// | 3     | ✅        | ❌      | The domain name account                                                   |
+    /// | 4     | ❌        | ❌      | The SPL token program account                                             |
+    /// | 5     | ❌        | ❌      | The SPL name service program account                                      |
+    /// | 6     | ✅        | ✅      | The account paying for a larger registry (and refunded for a smaller one) |
+    /// | 7     | ❌        | ❌      | The system program account                                                |
+    /// | 8     | ❌        | ✅      | The class of the domain name (omitted if the domain has no class)         |
+    ManageData,
+    /// Invoke an allowed program with the `NftRecord` of a tokenized domain name as signer
+    ///
+    /// | Index | Writable | Signer | Description               |
+    /// | ----------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The NFT owner account     |
+    /// | 1     | ❌        | ❌      | The NFT account           |
+    /// | 2     | ❌        | ❌      | The NFT record account    |
+    /// | 3     | ❌        | ❌      | The central state account |
+    /// | 4     | ❌        | ❌      | The invoked program       |
+    Execute,
+    /// Set the owner of an active NFT record to the current NFT holder
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The NFT record account            |
+    /// | 1     | ❌        | ❌      | The token account holding the NFT |
+    SyncOwner,
+    /// Create the NFT mint of a domain name if needed and tokenize the domain
+    ///
+    /// | Index | Writable | Signer | Description                                                                                 |
+    /// | ----------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The mint of the NFT                                                                         |
+    /// | 1     | ✅        | ❌      | The NFT token destination                                                                   |
+    /// | 2     | ✅        | ❌      | The domain name account                                                                     |
+    /// | 3     | ✅        | ❌      | The NFT record account                                                                      |
+    /// | 4     | ✅        | ✅      | The domain name owner                                                                       |
+    /// | 5     | ✅        | ❌      | The metadata account                                                                        |
+    /// | 6     | ❌        | ❌      | Master edition account                                                                      |
+    /// | 7     | ❌        | ❌      | Collection                                                                                  |
+    /// | 8     | ❌        | ❌      | Mint of the collection                                                                      |
+    /// | 9     | ✅        | ❌      | The central state account                                                                   |
+    /// | 10    | ✅        | ✅      | The fee payer account                                                                       |
+    /// | 11    | ❌        | ❌      | The SPL token program account                                                               |
+    /// | 12    | ❌        | ❌      | The metadata program account                                                                |
+    /// | 13    | ❌        | ❌      | The system program account                                                                  |
+    /// | 14    | ❌        | ❌      | The SPL name service program account                                                        |
+    /// | 15    | ❌        | ❌      | Rent sysvar account                                                                         |
+    /// | 16    | ✅        | ❌      | The master edition account of the NFT                                                       |
+    /// | 17    | ✅        | ❌      | The central state escrow of the NFT                                                         |
+    /// | 18    | ❌        | ❌      | The associated token account program account                                                |
+    /// | 19    | ❌        | ❌      | The instructions sysvar account                                                             |
+    /// | 20    | ❌        | ❌      | The metadata signer, co-signing custom URIs that are not attested                           |
+    /// | 21    | ✅        | ❌      | The account paying the protocol fee (the fee payer for SOL fees, or its token account)      |
+    /// | 22    | ✅        | ❌      | The fee vault of the central state                                                          |
+    /// | 23    | ✅        | ❌      | The referrer receiving a share of the protocol fee (the fee vault if there is none)         |
+    /// | 24    | ❌        | ❌      | The TLD record account (omitted for .sol)                                                   |
+    /// | 25    | ❌        | ✅      | The class of the domain name (omitted if the domain has no class)                           |
+    /// | 26    | ❌        | ❌      | The parent domain when a subdomain is tokenized by the owner of its parent                  |
+    /// | 27    | ❌        | ❌      | The NFT record of the parent domain if it is tokenized                                      |
+    /// | 28    | ❌        | ❌      | The token account of the name owner holding the NFT of the parent domain if it is tokenized |
+    CreateMintAndNft,
+    /// Return the effective owner of a domain name through the return data
+    ///
+    /// | Index | Writable | Signer | Description                                                                |
+    /// | ------------------------------------------------------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The domain name account                                                    |
+    /// | 1     | ❌        | ❌      | The NFT record account of the domain (which may not exist)                 |
+    /// | 2     | ❌        | ❌      | The token account holding the NFT (omitted if the domain is not tokenized) |
+    GetEffectiveOwner,
 }
 #[allow(missing_docs)]
 pub fn create_mint(
+    program_id: Pubkey,
     accounts: create_mint::Accounts<Pubkey>,
     params: create_mint::Params,
 ) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::CreateMint as u8, params)
+    accounts.get_instruction(program_id, ProgramInstruction::CreateMint as u8, params)
 }
 #[allow(missing_docs)]
 pub fn create_nft(
+    program_id: Pubkey,
     accounts: create_nft::Accounts<Pubkey>,
     params: create_nft::Params,
 ) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::CreateNft as u8, params)
+    let custom_uri = !params.uri.is_empty();
+    let mut ix = create_nft_instruction(program_id, accounts, params);
+    // The metadata signer only co-signs custom URIs on clusters that verify them
+    if let Some(metadata_signer) = Config::from_program_id(&program_id)
+        .ok()
+        .and_then(|c| c.metadata_signer)
+    {
+        ix.accounts
+            .iter_mut()
+            .filter(|m| m.pubkey == metadata_signer)
+            .for_each(|m| m.is_signer = custom_uri);
+    }
+    ix
+}
+
+/// Tokenize a domain name with a custom URI attested off-chain by the metadata signer.
+/// Returns the ed25519 verification instruction followed by the tokenization instruction.
+pub fn create_nft_attested(
+    program_id: Pubkey,
+    accounts: create_nft::Accounts<Pubkey>,
+    params: create_nft::Params,
+    expiry: i64,
+    signature: &[u8; 64],
+) -> [Instruction; 2] {
+    let message = create_nft::attestation_message(accounts.name_account, &params.uri, expiry);
+    let metadata_signer = *accounts.metadata_signer;
+    [
+        ed25519_instruction(&metadata_signer, signature, &message),
+        create_nft_instruction(program_id, accounts, params),
+    ]
+}
+
+/// Builds `create_nft`, the absent optional accounts followed by a present one are passed as
+/// the program ID so that the program reads the accounts at their position
+fn create_nft_instruction(
+    program_id: Pubkey,
+    accounts: create_nft::Accounts<Pubkey>,
+    params: create_nft::Params,
+) -> Instruction {
+    let mut accounts: create_nft::Accounts<'_, Pubkey> = accounts;
+    let mut optional = [
+        &mut accounts.tld_record,
+        &mut accounts.name_class,
+        &mut accounts.parent_name,
+        &mut accounts.parent_nft_record,
+        &mut accounts.parent_nft_account,
+    ];
+    let last = optional.iter().rposition(|a| a.is_some()).unwrap_or(0);
+    for account in optional.iter_mut().take(last) {
+        account.get_or_insert(&program_id);
+    }
+    let mut ix = accounts.get_instruction(program_id, ProgramInstruction::CreateNft as u8, params);
+    ix.accounts
+        .iter_mut()
+        .filter(|m| m.pubkey == program_id)
+        .for_each(|m| {
+            m.is_signer = false;
+            m.is_writable = false;
+        });
+    ix
+}
+
+/// An ed25519 program instruction verifying `signature` of `message` by `signer`
+pub fn ed25519_instruction(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    // num_signatures || padding || Ed25519SignatureOffsets || public key || signature || message
+    let public_key_offset: u16 = 16;
+    let signature_offset = public_key_offset + 32;
+    let message_data_offset = signature_offset + 64;
+    let offsets = [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_data_offset,
+        message.len() as u16,
+        u16::MAX,
+    ];
+    let mut data = vec![1, 0];
+    offsets
+        .iter()
+        .for_each(|o| data.extend_from_slice(&o.to_le_bytes()));
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
 }
 #[allow(missing_docs)]
 pub fn redeem_nft(
+    program_id: Pubkey,
     accounts: redeem_nft::Accounts<Pubkey>,
     params: redeem_nft::Params,
 ) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::RedeemNft as u8, params)
+    accounts.get_instruction(program_id, ProgramInstruction::RedeemNft as u8, params)
 }
 #[allow(missing_docs)]
 pub fn withdraw_tokens(
+    program_id: Pubkey,
     accounts: withdraw_tokens::Accounts<Pubkey>,
     params: withdraw_tokens::Params,
 ) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::WithdrawTokens as u8, params)
+    accounts.get_instruction(program_id, ProgramInstruction::WithdrawTokens as u8, params)
 }
 #[allow(missing_docs)]
 pub fn create_collection(
+    program_id: Pubkey,
     accounts: create_collection::Accounts<Pubkey>,
     params: create_collection::Params,
 ) -> Instruction {
     accounts.get_instruction(
-        crate::ID,
+        program_id,
         ProgramInstruction::CreateCollection as u8,
         params,
     )
 }
 #[allow(missing_docs)]
-pub fn edit_data(accounts: edit_data::Accounts<Pubkey>, params: edit_data::Params) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::EditData as u8, params)
+pub fn edit_data(
+    program_id: Pubkey,
+    accounts: edit_data::Accounts<Pubkey>,
+    params: edit_data::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::EditData as u8, params)
 }
 
 #[allow(missing_docs)]
 pub fn unverify_nft(
+    program_id: Pubkey,
     accounts: unverify_nft::Accounts<Pubkey>,
     params: unverify_nft::Params,
 ) -> Instruction {
-    accounts.get_instruction(crate::ID, ProgramInstruction::UnverifyNft as u8, params)
+    accounts.get_instruction(program_id, ProgramInstruction::UnverifyNft as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn register_tld(
+    program_id: Pubkey,
+    accounts: register_tld::Accounts<Pubkey>,
+    params: register_tld::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::RegisterTld as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn update_central_state(
+    program_id: Pubkey,
+    accounts: update_central_state::Accounts<Pubkey>,
+    params: update_central_state::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::UpdateCentralState as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn edit_creators(
+    program_id: Pubkey,
+    accounts: edit_creators::Accounts<Pubkey>,
+    params: edit_creators::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::EditCreators as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn withdraw_fees(
+    program_id: Pubkey,
+    accounts: withdraw_fees::Accounts<Pubkey>,
+    params: withdraw_fees::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::WithdrawFees as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn set_lock_authority(
+    program_id: Pubkey,
+    accounts: set_lock_authority::Accounts<Pubkey>,
+    params: set_lock_authority::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::SetLockAuthority as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn set_lock(
+    program_id: Pubkey,
+    accounts: set_lock::Accounts<Pubkey>,
+    params: set_lock::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::SetLock as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn manage_data(
+    program_id: Pubkey,
+    accounts: manage_data::Accounts<Pubkey>,
+    params: manage_data::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::ManageData as u8, params)
+}
+
+/// Invoke `instruction` with the `NftRecord` as signer, the accounts of `instruction` are appended
+/// to the accounts of the tokenizer
+pub fn execute(
+    program_id: Pubkey,
+    accounts: execute::Accounts<Pubkey>,
+    instruction: Instruction,
+) -> Instruction {
+    let nft_record = *accounts.nft_record;
+    let mut ix = accounts.get_instruction(
+        program_id,
+        ProgramInstruction::Execute as u8,
+        execute::Params {
+            data: instruction.data,
+        },
+    );
+    // The record signs through the tokenizer
+    ix.accounts
+        .extend(instruction.accounts.into_iter().map(|mut m| {
+            m.is_signer &= m.pubkey != nft_record;
+            m
+        }));
+    ix
+}
+
+#[allow(missing_docs)]
+pub fn sync_owner(
+    program_id: Pubkey,
+    accounts: sync_owner::Accounts<Pubkey>,
+    params: sync_owner::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::SyncOwner as u8, params)
+}
+
+/// Create the NFT mint of a domain name if needed and tokenize the domain,
+/// see `cpi::create_mint_and_nft` to invoke it from another program
+pub fn create_mint_and_nft(
+    program_id: Pubkey,
+    accounts: create_mint_and_nft::Accounts<Pubkey>,
+    params: create_mint_and_nft::Params,
+) -> Instruction {
+    let mut ix = create_nft(program_id, accounts, params);
+    ix.data[0] = ProgramInstruction::CreateMintAndNft as u8;
+    ix
+}
+
+#[allow(missing_docs)]
+pub fn get_effective_owner(
+    program_id: Pubkey,
+    accounts: get_effective_owner::Accounts<Pubkey>,
+    params: get_effective_owner::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::GetEffectiveOwner as u8,
+        params,
+    )
 }
@@ -1,5 +1,15 @@
 use bonfida_utils::declare_id_with_central_state;
 
+/// Logs a diagnostic message, compiled out unless the `verbose-logs` feature is enabled
+macro_rules! verbose_msg {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "verbose-logs")]
+        solana_program::msg!($($arg)*);
+    };
+}
+
+/// Cluster specific configuration
+pub mod config;
 #[doc(hidden)]
 pub mod entrypoint;
 #[doc(hidden)]
@@ -2,7 +2,7 @@ use {
     borsh::BorshDeserialize,
     num_traits::FromPrimitive,
     solana_program::{
-        account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+        account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
         pubkey::Pubkey,
     },
 };
@@ -11,10 +11,21 @@ use crate::instruction::ProgramInstruction;
 
 pub mod create_collection;
 pub mod create_mint;
+pub mod create_mint_and_nft;
 pub mod create_nft;
+pub mod edit_creators;
 pub mod edit_data;
+pub mod execute;
+pub mod get_effective_owner;
+pub mod manage_data;
 pub mod redeem_nft;
+pub mod register_tld;
+pub mod set_lock;
+pub mod set_lock_authority;
+pub mod sync_owner;
 pub mod unverify_nft;
+pub mod update_central_state;
+pub mod withdraw_fees;
 pub mod withdraw_tokens;
 
 pub struct Processor {}
@@ -25,47 +36,115 @@ impl Processor {
         accounts: &[AccountInfo],
         instruction_data: &[u8],
     ) -> ProgramResult {
-        msg!("Beginning processing");
+        verbose_msg!("Beginning processing");
         let instruction = FromPrimitive::from_u8(instruction_data[0])
             .ok_or(ProgramError::InvalidInstructionData)?;
         let instruction_data = &instruction_data[1..];
-        msg!("Instruction unpacked");
+        verbose_msg!("Instruction unpacked");
 
         match instruction {
             ProgramInstruction::CreateMint => {
-                msg!("Instruction: Create mint");
+                verbose_msg!("Instruction: Create mint");
                 create_mint::process(program_id, accounts)?;
             }
             ProgramInstruction::CreateCollection => {
-                msg!("Instruction: Create collection");
+                verbose_msg!("Instruction: Create collection");
                 create_collection::process(program_id, accounts)?;
             }
             ProgramInstruction::CreateNft => {
-                msg!("Instruction: Create NFT");
+                verbose_msg!("Instruction: Create NFT");
                 let params = create_nft::Params::try_from_slice(instruction_data)
                     .map_err(|_| ProgramError::InvalidInstructionData)?;
                 create_nft::process(program_id, accounts, params)?;
             }
             ProgramInstruction::RedeemNft => {
-                msg!("Instruction: Redeem NFT");
-                redeem_nft::process(program_id, accounts)?;
+                verbose_msg!("Instruction: Redeem NFT");
+                let params = redeem_nft::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                redeem_nft::process(program_id, accounts, params)?;
             }
             ProgramInstruction::WithdrawTokens => {
-                msg!("Instruction: Withdraw tokens");
+                verbose_msg!("Instruction: Withdraw tokens");
                 withdraw_tokens::process(program_id, accounts)?
             }
             ProgramInstruction::EditData => {
-                msg!("Instruction: Edit data");
+                verbose_msg!("Instruction: Edit data");
                 let params = edit_data::Params::try_from_slice(instruction_data)
                     .map_err(|_| ProgramError::InvalidInstructionData)?;
                 edit_data::process(program_id, accounts, params)?
             }
             ProgramInstruction::UnverifyNft => {
-                msg!("Instruction: Unverify NFT");
+                verbose_msg!("Instruction: Unverify NFT");
                 let params = unverify_nft::Params::try_from_slice(instruction_data)
                     .map_err(|_| ProgramError::InvalidInstructionData)?;
                 unverify_nft::process(program_id, accounts, params)?
             }
+            ProgramInstruction::RegisterTld => {
+                verbose_msg!("Instruction: Register TLD");
+                let params = register_tld::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                register_tld::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::UpdateCentralState => {
+                verbose_msg!("Instruction: Update central state");
+                let params = update_central_state::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                update_central_state::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::EditCreators => {
+                verbose_msg!("Instruction: Edit creators");
+                let params = edit_creators::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                edit_creators::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::WithdrawFees => {
+                verbose_msg!("Instruction: Withdraw fees");
+                let params = withdraw_fees::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                withdraw_fees::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::SetLockAuthority => {
+                verbose_msg!("Instruction: Set lock authority");
+                let params = set_lock_authority::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                set_lock_authority::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::SetLock => {
+                verbose_msg!("Instruction: Set lock");
+                let params = set_lock::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                set_lock::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::ManageData => {
+                verbose_msg!("Instruction: Manage data");
+                let params = manage_data::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                manage_data::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::Execute => {
+                verbose_msg!("Instruction: Execute");
+                let params = execute::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                execute::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::SyncOwner => {
+                verbose_msg!("Instruction: Sync owner");
+                let params = sync_owner::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                sync_owner::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::CreateMintAndNft => {
+                verbose_msg!("Instruction: Create mint and NFT");
+                let params = create_mint_and_nft::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_mint_and_nft::process(program_id, accounts, params)?
+            }
+            ProgramInstruction::GetEffectiveOwner => {
+                verbose_msg!("Instruction: Get effective owner");
+                let params = get_effective_owner::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                get_effective_owner::process(program_id, accounts, params)?
+            }
         }
 
         Ok(())
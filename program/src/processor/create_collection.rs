@@ -11,8 +11,10 @@ use mpl_token_metadata::{
 };
 
 use crate::{
+    config::Config,
     cpi::Cpi,
-    state::{COLLECTION_NAME, COLLECTION_PREFIX, COLLECTION_URI, META_SYMBOL},
+    state::COLLECTION_PREFIX,
+    utils::{check_program_mint, get_collection, next_optional_account_info},
 };
 
 use {
@@ -25,7 +27,6 @@ use {
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
-        msg,
         program::{invoke, invoke_signed},
         program_error::ProgramError,
         program_pack::Pack,
@@ -81,12 +82,15 @@ pub struct Accounts<'a, T> {
 
     /// Rent sysvar account
     pub rent_account: &'a T,
+
+    /// The TLD record account (omitted for .sol)
+    pub tld_record: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     pub fn parse(
         accounts: &'a [AccountInfo<'b>],
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
@@ -102,10 +106,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             spl_name_service_program: next_account_info(accounts_iter)?,
             ata_program: next_account_info(accounts_iter)?,
             rent_account: next_account_info(accounts_iter)?,
+            tld_record: next_optional_account_info(accounts_iter, program_id),
         };
 
         // Check keys
-        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
         check_account_key(accounts.spl_token_program, &spl_token::ID)?;
         check_account_key(accounts.metadata_program, &mpl_token_metadata::ID)?;
         check_account_key(accounts.system_program, &system_program::ID)?;
@@ -113,11 +119,14 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         check_account_key(accounts.ata_program, &spl_associated_token_account::ID)?;
         check_account_key(accounts.rent_account, &sysvar::rent::ID)?;
 
-        // Check owners
-        check_account_owner(accounts.collection_mint, &system_program::ID)?;
-        check_account_owner(accounts.edition, &system_program::ID)?;
-        check_account_owner(accounts.metadata_account, &system_program::ID)?;
-        check_account_owner(accounts.central_state_nft_ata, &system_program::ID)?;
+        // Check owners, the accounts of an existing collection are verified when processing
+        if accounts.collection_mint.owner == &system_program::ID {
+            check_account_owner(accounts.edition, &system_program::ID)?;
+            check_account_owner(accounts.metadata_account, &system_program::ID)?;
+            check_account_owner(accounts.central_state_nft_ata, &system_program::ID)?;
+        } else {
+            check_account_owner(accounts.collection_mint, &spl_token::ID)?;
+        }
 
         // Check signer
         check_signer(accounts.fee_payer)?;
@@ -128,24 +137,40 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
 
-    let (collection_mint, collection_mint_nonce) =
-        Pubkey::find_program_address(&[COLLECTION_PREFIX, &program_id.to_bytes()], program_id);
+    let collection = get_collection(program_id, accounts.tld_record)?;
+    let collection_mint = collection.mint;
     check_account_key(accounts.collection_mint, &collection_mint)?;
 
-    let (metadata_key, _) = Metadata::find_pda(&collection_mint);
+    let metadata_key = Metadata::create_pda(collection_mint, collection.metadata_nonce)?;
     check_account_key(accounts.metadata_account, &metadata_key)?;
 
-    let (edition_key, _) = MasterEdition::find_pda(&collection_mint);
+    let edition_key = MasterEdition::create_pda(collection_mint, collection.edition_nonce)?;
     check_account_key(accounts.edition, &edition_key)?;
 
+    // The mint, its metadata and its master edition are created together
+    if accounts.collection_mint.owner == &spl_token::ID {
+        verbose_msg!("+ Collection already created");
+        check_program_mint(
+            accounts.collection_mint,
+            &edition_key,
+            &config.central_state,
+        )?;
+        return check_account_owner(accounts.edition, &mpl_token_metadata::ID);
+    }
+
     // Create mint account
-    msg!("+ Creating mint");
-    let seeds: &[&[u8]] = &[
-        COLLECTION_PREFIX,
-        &program_id.to_bytes(),
-        &[collection_mint_nonce],
-    ];
+    verbose_msg!("+ Creating mint");
+    let program_id_bytes = program_id.to_bytes();
+    let tld_bytes = collection.tld.to_bytes();
+    let collection_mint_nonce = [collection.nonce];
+    let mut seeds: Vec<&[u8]> = vec![COLLECTION_PREFIX, &program_id_bytes];
+    if collection.tld != config.root_domain {
+        seeds.push(&tld_bytes);
+    }
+    seeds.push(&collection_mint_nonce);
+    let seeds = seeds.as_slice();
     Cpi::create_account(
         &spl_token::ID,
         accounts.system_program,
@@ -154,13 +179,13 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         seeds,
         Mint::LEN,
     )?;
-    msg!("+ Initialize mint");
+    verbose_msg!("+ Initialize mint");
     // Initialize mint
     let ix = initialize_mint(
         &spl_token::ID,
         &collection_mint,
-        &crate::central_state::KEY,
-        Some(&crate::central_state::KEY),
+        &config.central_state,
+        Some(&config.central_state),
         0,
     )?;
     invoke_signed(
@@ -174,10 +199,10 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     )?;
 
     // Create central state ATA
-    msg!("+ Creating central state ATA");
+    verbose_msg!("+ Creating central state ATA");
     let ix = create_associated_token_account(
         accounts.fee_payer.key,
-        &crate::central_state::KEY,
+        &config.central_state,
         &collection_mint,
         &spl_token::ID,
     );
@@ -197,13 +222,13 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
 
     // Mint NFT
     // (because the master edition ix requires mint supply === 1)
-    msg!("+ Minting NFT");
-    let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[crate::central_state::NONCE]];
+    verbose_msg!("+ Minting NFT");
+    let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[config.central_state_nonce]];
     let ix = mint_to(
         &spl_token::ID,
         &collection_mint,
         accounts.central_state_nft_ata.key,
-        &crate::central_state::KEY,
+        &config.central_state,
         &[],
         1,
     )?;
@@ -220,9 +245,9 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     )?;
 
     // Create collection
-    msg!("+ Creating collection");
+    verbose_msg!("+ Creating collection");
     let central_creator = Creator {
-        address: crate::central_state::KEY,
+        address: config.central_state,
         verified: true,
         share: 100,
     };
@@ -239,9 +264,9 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         },
         CreateMetadataAccountV3InstructionArgs {
             data: DataV2 {
-                name: COLLECTION_NAME.to_string(),
-                uri: COLLECTION_URI.to_string(),
-                symbol: META_SYMBOL.to_string(),
+                name: collection.name,
+                uri: collection.uri,
+                symbol: collection.symbol,
                 seller_fee_basis_points: 0,
                 creators: Some(vec![central_creator]),
                 uses: None,
@@ -254,7 +279,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     .invoke_signed(&[seeds])?;
 
     // Create master edition
-    msg!("+ Creating master edition");
+    verbose_msg!("+ Creating master edition");
     CreateMasterEditionV3Cpi::new(
         accounts.metadata_program,
         CreateMasterEditionV3CpiAccounts {
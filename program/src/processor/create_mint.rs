@@ -1,6 +1,6 @@
 //! Create the NFT mint
 
-use crate::{cpi::Cpi, state::MINT_PREFIX};
+use crate::{config::Config, cpi::Cpi, state::MINT_PREFIX, utils::check_program_mint};
 
 use {
     bonfida_utils::{
@@ -8,10 +8,10 @@ use {
         BorshSize, InstructionsAccount,
     },
     borsh::{BorshDeserialize, BorshSerialize},
+    mpl_token_metadata::accounts::MasterEdition,
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
-        msg,
         program::invoke_signed,
         program_error::ProgramError,
         program_pack::Pack,
@@ -53,7 +53,7 @@ pub struct Accounts<'a, T> {
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     pub fn parse(
         accounts: &'a [AccountInfo<'b>],
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
@@ -67,13 +67,15 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         };
 
         // Check keys
-        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
         check_account_key(accounts.spl_token_program, &spl_token::ID)?;
         check_account_key(accounts.system_program, &system_program::ID)?;
         check_account_key(accounts.rent_account, &sysvar::rent::ID)?;
 
         // Check owners
-        check_account_owner(accounts.mint, &system_program::ID)?;
+        check_account_owner(accounts.mint, &system_program::ID)
+            .or_else(|_| check_account_owner(accounts.mint, &spl_token::ID))?;
         check_account_owner(accounts.name_account, &spl_name_service::ID)?;
 
         // Check signer
@@ -85,6 +87,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
 
     let (mint, mint_nonce) = Pubkey::find_program_address(
         &[MINT_PREFIX, &accounts.name_account.key.to_bytes()],
@@ -92,7 +95,13 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     );
     check_account_key(accounts.mint, &mint)?;
 
-    msg!("+ Creating mint");
+    if accounts.mint.owner == &spl_token::ID {
+        verbose_msg!("+ Mint already created");
+        let (edition_key, _) = MasterEdition::find_pda(&mint);
+        return check_program_mint(accounts.mint, &edition_key, &config.central_state);
+    }
+
+    verbose_msg!("+ Creating mint");
 
     // Create mint account
     let seeds: &[&[u8]] = &[
@@ -113,8 +122,8 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let ix = initialize_mint(
         &spl_token::ID,
         &mint,
-        &crate::central_state::KEY,
-        Some(&crate::central_state::KEY),
+        &config.central_state,
+        Some(&config.central_state),
         0,
     )?;
     invoke_signed(
@@ -0,0 +1,65 @@
+//! Create the NFT mint of a domain name if needed and tokenize the domain in a single instruction
+//!
+//! The accounts and parameters are the ones of `create_nft`, which lets registrar programs
+//! register and tokenize a domain within one transaction. When the NFT destination does not
+//! exist yet, it is created as the associated token account of the name owner.
+
+use {
+    bonfida_utils::checks::check_account_key,
+    solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, program::invoke, pubkey::Pubkey,
+    },
+    spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account_idempotent,
+    },
+};
+
+use super::{create_mint, create_nft};
+
+pub use super::create_nft::{Accounts, Params};
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    // The owners of the mint and the NFT destination are checked by `create_nft` once created
+    let parsed = Accounts::parse_keys(accounts, program_id)?;
+
+    if parsed.mint.data_is_empty() {
+        let mint_accounts = [
+            parsed.mint.clone(),
+            parsed.name_account.clone(),
+            parsed.central_state.clone(),
+            parsed.spl_token_program.clone(),
+            parsed.system_program.clone(),
+            parsed.rent_account.clone(),
+            parsed.fee_payer.clone(),
+        ];
+        create_mint::process(program_id, &mint_accounts)?;
+    }
+
+    if parsed.nft_destination.data_is_empty() {
+        verbose_msg!("+ Creating NFT destination");
+        check_account_key(
+            parsed.nft_destination,
+            &get_associated_token_address(parsed.name_owner.key, parsed.mint.key),
+        )?;
+        let ix = create_associated_token_account_idempotent(
+            parsed.fee_payer.key,
+            parsed.name_owner.key,
+            parsed.mint.key,
+            &spl_token::ID,
+        );
+        invoke(
+            &ix,
+            &[
+                parsed.ata_program.clone(),
+                parsed.fee_payer.clone(),
+                parsed.nft_destination.clone(),
+                parsed.name_owner.clone(),
+                parsed.mint.clone(),
+                parsed.system_program.clone(),
+                parsed.spl_token_program.clone(),
+            ],
+        )?;
+    }
+
+    create_nft::process(program_id, accounts, params)
+}
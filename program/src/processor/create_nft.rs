@@ -1,10 +1,14 @@
 //! Tokenize a domain name
 
+use std::convert::TryInto;
+
 use mpl_token_metadata::{
     accounts::{MasterEdition, Metadata},
     instructions::{
-        CreateMetadataAccountV3Cpi, CreateMetadataAccountV3CpiAccounts,
-        CreateMetadataAccountV3InstructionArgs, SetAndVerifyCollectionCpi,
+        CreateMasterEditionV3Cpi, CreateMasterEditionV3CpiAccounts,
+        CreateMasterEditionV3InstructionArgs, CreateMetadataAccountV3Cpi,
+        CreateMetadataAccountV3CpiAccounts, CreateMetadataAccountV3InstructionArgs,
+        FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts, SetAndVerifyCollectionCpi,
         SetAndVerifyCollectionCpiAccounts, UnverifyCollectionCpi, UnverifyCollectionCpiAccounts,
         UpdateMetadataAccountV2Cpi, UpdateMetadataAccountV2CpiAccounts,
         UpdateMetadataAccountV2InstructionArgs,
@@ -12,12 +16,15 @@ use mpl_token_metadata::{
 };
 
 use crate::{
+    config::Config,
     cpi::Cpi,
     state::{
-        NftRecord, Tag, COLLECTION_NAME, COLLECTION_PREFIX, CREATOR_FEE, METADATA_SIGNER,
-        META_SYMBOL, MINT_PREFIX, SELLER_BASIS,
+        CentralState, NftRecord, NftRecordMut, NftRecordNonces, Tag, MINT_PREFIX, SELLER_BASIS,
+    },
+    utils::{
+        check_creator_share, check_name, check_name_class, collect_fee, get_collection, get_fees,
+        next_optional_account_info, nft_creators, wipe_name_data,
     },
-    utils::check_name,
 };
 
 use {
@@ -26,28 +33,221 @@ use {
         BorshSize, InstructionsAccount,
     },
     borsh::{BorshDeserialize, BorshSerialize},
-    mpl_token_metadata::types::{Creator, DataV2},
+    mpl_token_metadata::{types::DataV2, MAX_NAME_LENGTH},
     solana_program::{
         account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        ed25519_program,
         entrypoint::ProgramResult,
         msg,
         program::{invoke, invoke_signed},
         program_error::ProgramError,
         program_pack::Pack,
         pubkey::Pubkey,
-        system_program, sysvar,
+        system_program,
+        sysvar::{
+            self,
+            instructions::{load_current_index_checked, load_instruction_at_checked},
+            Sysvar,
+        },
+    },
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+    spl_name_service::{instruction::transfer, state::NameRecordHeader},
+    spl_token::{
+        instruction::{approve, mint_to},
+        state::{Account, Mint},
     },
-    spl_name_service::instruction::transfer,
-    spl_token::{instruction::mint_to, state::Mint},
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
-    /// The domain name (without .sol)
+    /// The domain name (without .sol), `sub.parent` for subdomains, the metadata name is
+    /// `metadata_name(name)`
     pub name: String,
 
-    /// The URI of the metadata
+    /// The URI of the metadata, signed or attested by the metadata signer
+    /// (if empty the URI is derived from the central state configuration)
     pub uri: String,
+
+    /// Share of the royalties (in percent) of the name owner as a creator of the NFT
+    pub creator_share: u8,
+
+    /// Freeze the NFT in the destination account, which must belong to the name owner
+    pub soulbound: bool,
+
+    /// Zero the data of the name account once it is held by the NFT record
+    pub wipe_data: bool,
+}
+
+/// The message the metadata signer attests with an ed25519 signature instead of co-signing
+pub fn attestation_message(name_account: &Pubkey, uri: &str, expiry: i64) -> Vec<u8> {
+    [name_account.as_ref(), &expiry.to_le_bytes(), uri.as_bytes()].concat()
+}
+
+/// Verifies that the previous instruction of the transaction is an ed25519 signature of
+/// `metadata_signer` over the attestation of `uri` for `name_account`, and that it has not expired
+fn check_metadata_attestation(
+    instructions_sysvar: &AccountInfo,
+    metadata_signer: &Pubkey,
+    name_account: &Pubkey,
+    uri: &str,
+) -> ProgramResult {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        msg!("+ Missing metadata attestation");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    if ix.program_id != ed25519_program::ID {
+        msg!("+ Missing metadata attestation");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    verify_attestation(
+        &ix.data,
+        metadata_signer,
+        name_account,
+        uri,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+/// Verifies that the data of an ed25519 program instruction is a signature of `metadata_signer`
+/// over the attestation of `uri` for `name_account` which has not expired at `now`
+pub fn verify_attestation(
+    data: &[u8],
+    metadata_signer: &Pubkey,
+    name_account: &Pubkey,
+    uri: &str,
+    now: i64,
+) -> ProgramResult {
+    let (signer, message) = parse_ed25519_instruction(data)?;
+    if signer != metadata_signer.as_ref() {
+        msg!("+ Attestation is not signed by the metadata signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // name_account (32 bytes) || expiry (i64 LE) || uri
+    if message.len() != 40 + uri.len()
+        || &message[..32] != name_account.as_ref()
+        || &message[40..] != uri.as_bytes()
+    {
+        msg!("+ Attestation does not match the domain and URI");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let expiry = i64::from_le_bytes(message[32..40].try_into().unwrap());
+    if now > expiry {
+        msg!("+ Attestation expired at {}", expiry);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Returns the public key and the message of an ed25519 program instruction verifying a single
+/// signature with all its data contained in the instruction itself
+pub fn parse_ed25519_instruction(data: &[u8]) -> Result<(&[u8], &[u8]), ProgramError> {
+    // num_signatures (u8) || padding (u8) || Ed25519SignatureOffsets (7 * u16)
+    if data.len() < 16 || data[0] != 1 {
+        msg!("+ Expected a single ed25519 signature");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let signature_instruction_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_instruction_index = read_u16(8);
+    let message_data_offset = read_u16(10) as usize;
+    let message_data_size = read_u16(12) as usize;
+    let message_instruction_index = read_u16(14);
+
+    // The signed data must live in the ed25519 instruction itself
+    if signature_instruction_index != u16::MAX
+        || public_key_instruction_index != u16::MAX
+        || message_instruction_index != u16::MAX
+    {
+        msg!("+ Ed25519 offsets must refer to the ed25519 instruction");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    Ok((public_key, message))
+}
+
+/// Prefix of the hashed names of subdomains
+const SUBDOMAIN_PREFIX: &str = "\0";
+
+/// Suffix of the metadata names that have been truncated
+pub const TRUNCATION_SUFFIX: &str = "...";
+
+/// The name of the NFT metadata for the domain `name`
+///
+/// Metaplex names are limited to `MAX_NAME_LENGTH` bytes, longer names are cut at the last
+/// character boundary that leaves room for `TRUNCATION_SUFFIX`, which is then appended.
+pub fn metadata_name(name: &str) -> String {
+    if name.len() <= MAX_NAME_LENGTH {
+        return name.to_string();
+    }
+    let max_len = MAX_NAME_LENGTH - TRUNCATION_SUFFIX.len();
+    let end = (0..=max_len)
+        .rev()
+        .find(|i| name.is_char_boundary(*i))
+        .unwrap_or_default();
+    format!("{}{}", &name[..end], TRUNCATION_SUFFIX)
+}
+
+/// Keys of the accounts of the NFT of a domain, with the nonces stored in its record
+struct NftKeys {
+    mint: Pubkey,
+    metadata: Pubkey,
+    edition: Pubkey,
+    escrow: Pubkey,
+    nonces: NftRecordNonces,
+}
+
+impl NftKeys {
+    /// Searches the keys, for new records and legacy records which do not store their nonces
+    fn find(config: &Config, name_account: &Pubkey) -> Self {
+        let (mint, mint_nonce) = Pubkey::find_program_address(
+            &[MINT_PREFIX, &name_account.to_bytes()],
+            &config.program_id,
+        );
+        let (metadata, metadata_nonce) = Metadata::find_pda(&mint);
+        let (edition, edition_nonce) = MasterEdition::find_pda(&mint);
+        let (escrow, escrow_nonce) = config.find_nft_escrow_key(&mint);
+        Self {
+            mint,
+            metadata,
+            edition,
+            escrow,
+            nonces: NftRecordNonces {
+                mint: mint_nonce,
+                metadata: metadata_nonce,
+                edition: edition_nonce,
+                escrow: escrow_nonce,
+            },
+        }
+    }
+
+    /// Derives the keys from the nonces stored in the record
+    fn from_nonces(
+        config: &Config,
+        mint: Pubkey,
+        nonces: NftRecordNonces,
+    ) -> Result<Self, ProgramError> {
+        Ok(Self {
+            mint,
+            metadata: Metadata::create_pda(mint, nonces.metadata)?,
+            edition: MasterEdition::create_pda(mint, nonces.edition)?,
+            escrow: config.nft_escrow_key(&mint, nonces.escrow)?,
+            nonces,
+        })
+    }
 }
 
 #[derive(InstructionsAccount)]
@@ -108,16 +308,83 @@ pub struct Accounts<'a, T> {
     /// Rent sysvar account
     pub rent_account: &'a T,
 
-    /// The metadata signer
-    #[cons(signer)]
-    #[cfg(not(feature = "devnet"))]
+    /// The master edition account of the NFT
+    #[cons(writable)]
+    pub nft_edition: &'a T,
+
+    /// The central state escrow of the NFT
+    #[cons(writable)]
+    pub nft_escrow: &'a T,
+
+    /// The associated token account program account
+    pub ata_program: &'a T,
+
+    /// The instructions sysvar account
+    pub instructions_sysvar: &'a T,
+
+    /// The metadata signer, co-signing custom URIs that are not attested
     pub metadata_signer: &'a T,
+
+    /// The account paying the protocol fee (the fee payer for SOL fees, or its token account)
+    #[cons(writable)]
+    pub fee_source: &'a T,
+
+    /// The fee vault of the central state
+    #[cons(writable)]
+    pub fee_vault: &'a T,
+
+    /// The referrer receiving a share of the protocol fee (the fee vault if there is none)
+    #[cons(writable)]
+    pub referrer: &'a T,
+
+    /// The TLD record account (omitted for .sol)
+    pub tld_record: Option<&'a T>,
+
+    /// The class of the domain name (omitted if the domain has no class)
+    #[cons(signer)]
+    pub name_class: Option<&'a T>,
+
+    /// The parent domain when a subdomain is tokenized by the owner of its parent
+    pub parent_name: Option<&'a T>,
+
+    /// The NFT record of the parent domain if it is tokenized
+    pub parent_nft_record: Option<&'a T>,
+
+    /// The token account of the name owner holding the NFT of the parent domain if it is tokenized
+    pub parent_nft_account: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     pub fn parse(
         accounts: &'a [AccountInfo<'b>],
         program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts = Self::parse_keys(accounts, program_id)?;
+
+        // Check owners
+        check_account_owner(accounts.mint, &spl_token::ID)?;
+        check_account_owner(accounts.nft_destination, &spl_token::ID)?;
+        check_account_owner(accounts.name_account, &spl_name_service::ID)?;
+        check_account_owner(accounts.nft_record, &system_program::ID)
+            .or_else(|_| check_account_owner(accounts.nft_record, program_id))?;
+        check_account_owner(accounts.metadata_account, &system_program::ID)
+            .or_else(|_| check_account_owner(accounts.metadata_account, &mpl_token_metadata::ID))?;
+        check_account_owner(accounts.edition_account, &mpl_token_metadata::ID)?;
+        check_account_owner(accounts.collection_metadata, &mpl_token_metadata::ID)?;
+        check_account_owner(accounts.collection_mint, &spl_token::ID)?;
+        check_account_owner(accounts.nft_edition, &system_program::ID)
+            .or_else(|_| check_account_owner(accounts.nft_edition, &mpl_token_metadata::ID))?;
+        check_account_owner(accounts.nft_escrow, &system_program::ID)
+            .or_else(|_| check_account_owner(accounts.nft_escrow, &spl_token::ID))?;
+
+        Ok(accounts)
+    }
+
+    /// Parses the accounts and checks their keys and signers, but not their owners, so that
+    /// `create_mint_and_nft` can create the mint and the NFT destination first
+    pub fn parse_keys(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
@@ -137,36 +404,37 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             system_program: next_account_info(accounts_iter)?,
             spl_name_service_program: next_account_info(accounts_iter)?,
             rent_account: next_account_info(accounts_iter)?,
-            #[cfg(not(feature = "devnet"))]
+            nft_edition: next_account_info(accounts_iter)?,
+            nft_escrow: next_account_info(accounts_iter)?,
+            ata_program: next_account_info(accounts_iter)?,
+            instructions_sysvar: next_account_info(accounts_iter)?,
             metadata_signer: next_account_info(accounts_iter)?,
+            fee_source: next_account_info(accounts_iter)?,
+            fee_vault: next_account_info(accounts_iter)?,
+            referrer: next_account_info(accounts_iter)?,
+            tld_record: next_optional_account_info(accounts_iter, program_id),
+            name_class: next_optional_account_info(accounts_iter, program_id),
+            parent_name: next_optional_account_info(accounts_iter, program_id),
+            parent_nft_record: next_optional_account_info(accounts_iter, program_id),
+            parent_nft_account: next_optional_account_info(accounts_iter, program_id),
         };
 
         // Check keys
-        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
         check_account_key(accounts.spl_token_program, &spl_token::ID)?;
         check_account_key(accounts.metadata_program, &mpl_token_metadata::ID)?;
         check_account_key(accounts.system_program, &system_program::ID)?;
         check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
         check_account_key(accounts.rent_account, &sysvar::rent::ID)?;
-        #[cfg(not(feature = "devnet"))]
-        check_account_key(accounts.metadata_signer, &METADATA_SIGNER)?;
-
-        // Check owners
-        check_account_owner(accounts.mint, &spl_token::ID)?;
-        check_account_owner(accounts.nft_destination, &spl_token::ID)?;
-        check_account_owner(accounts.name_account, &spl_name_service::ID)?;
-        check_account_owner(accounts.nft_record, &system_program::ID)
-            .or_else(|_| check_account_owner(accounts.nft_record, program_id))?;
-        check_account_owner(accounts.metadata_account, &system_program::ID)
-            .or_else(|_| check_account_owner(accounts.metadata_account, &mpl_token_metadata::ID))?;
-        check_account_owner(accounts.edition_account, &mpl_token_metadata::ID)?;
-        check_account_owner(accounts.collection_metadata, &mpl_token_metadata::ID)?;
-        check_account_owner(accounts.collection_mint, &spl_token::ID)?;
+        check_account_key(accounts.ata_program, &spl_associated_token_account::ID)?;
+        check_account_key(accounts.instructions_sysvar, &sysvar::instructions::ID)?;
+        if let Some(metadata_signer) = &config.metadata_signer {
+            check_account_key(accounts.metadata_signer, metadata_signer)?;
+        }
 
         // Check signer
         check_signer(accounts.name_owner)?;
-        #[cfg(not(feature = "devnet"))]
-        check_signer(accounts.metadata_signer)?;
 
         Ok(accounts)
     }
@@ -174,52 +442,197 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
-    let Params { name, uri } = params;
-
-    let (mint, _) = Pubkey::find_program_address(
-        &[MINT_PREFIX, &accounts.name_account.key.to_bytes()],
-        program_id,
-    );
+    let config = Config::from_program_id(program_id)?;
+    let Params {
+        name,
+        uri,
+        creator_share,
+        soulbound,
+        wipe_data,
+    } = params;
+
+    // Existing records store their nonces and mint, only new and legacy records search them
+    let (nft_record_nonce, stored) = if accounts.nft_record.data_is_empty() {
+        let (nft_record_key, nft_record_nonce) =
+            NftRecord::find_key(accounts.name_account.key, program_id);
+        check_account_key(accounts.nft_record, &nft_record_key)?;
+        (nft_record_nonce, None)
+    } else {
+        check_account_owner(accounts.nft_record, program_id)?;
+        let nft_record = NftRecordMut::from_account_info(accounts.nft_record, Tag::InactiveRecord)?;
+        nft_record.check_key(accounts.nft_record, accounts.name_account.key, program_id)?;
+        let stored = nft_record
+            .nonces()
+            .map(|nonces| (nft_record.base.nft_mint, nonces));
+        (nft_record.base.nonce, stored)
+    };
+    let nft_keys = match stored {
+        Some((mint, nonces)) => NftKeys::from_nonces(config, mint, nonces)?,
+        None => NftKeys::find(config, accounts.name_account.key),
+    };
+    let mint = nft_keys.mint;
     check_account_key(accounts.mint, &mint)?;
 
-    // Create NFT record
-    let (nft_record_key, nft_record_nonce) =
-        NftRecord::find_key(accounts.name_account.key, program_id);
-    check_account_key(accounts.nft_record, &nft_record_key)?;
+    // Verify the collection matches the TLD of the domain
+    let collection = get_collection(program_id, accounts.tld_record)?;
+    let header = NameRecordHeader::unpack_from_slice(&accounts.name_account.data.borrow())?;
+    let parent = match accounts.parent_name {
+        Some(parent_name) if header.parent_name != collection.tld => {
+            check_account_key(parent_name, &header.parent_name)?;
+            let parent_header = NameRecordHeader::unpack_from_slice(&parent_name.data.borrow())?;
+            if parent_header.parent_name != collection.tld {
+                msg!(
+                    "+ TLD mismatch, parent domain parent is {}",
+                    parent_header.parent_name
+                );
+                return Err(ProgramError::InvalidArgument);
+            }
+            Some((parent_name, parent_header))
+        }
+        _ if header.parent_name != collection.tld => {
+            msg!("+ TLD mismatch, domain parent is {}", header.parent_name);
+            return Err(ProgramError::InvalidArgument);
+        }
+        _ => None,
+    };
 
     // Verify name derivation
-    check_name(&name, accounts.name_account)?;
+    match &parent {
+        Some((parent_name, parent_header)) => {
+            let (sub, parent) = name.split_once('.').ok_or_else(|| {
+                msg!("+ Subdomain names must be of the form sub.parent");
+                ProgramError::InvalidArgument
+            })?;
+            check_name(parent, parent_name, &parent_header.class, &collection.tld)?;
+            check_name(
+                &format!("{}{}", SUBDOMAIN_PREFIX, sub),
+                accounts.name_account,
+                &header.class,
+                parent_name.key,
+            )?;
+        }
+        None => check_name(&name, accounts.name_account, &header.class, &collection.tld)?,
+    }
+    let class = check_name_class(&header.class, accounts.name_class)?;
+
+    // Verify the signer owns the domain, or its parent domain directly or through its NFT
+    let mut parent_nft_record_nonce = None;
+    match &parent {
+        _ if &header.owner == accounts.name_owner.key => (),
+        Some((_, parent_header)) if &parent_header.owner == accounts.name_owner.key => (),
+        Some((parent_name, parent_header)) => {
+            let (parent_nft_record, parent_nft_account) =
+                match (accounts.parent_nft_record, accounts.parent_nft_account) {
+                    (Some(record), Some(account)) => (record, account),
+                    _ => {
+                        msg!("+ The signer does not own the domain or its parent");
+                        return Err(ProgramError::InvalidArgument);
+                    }
+                };
+            check_account_key(parent_nft_record, &parent_header.owner)?;
+            check_account_owner(parent_nft_record, program_id)?;
+            check_account_owner(parent_nft_account, &spl_token::ID)?;
+
+            let parent_record = NftRecord::from_account_info(parent_nft_record, Tag::ActiveRecord)?;
+            parent_record.check_key(parent_nft_record, parent_name.key, program_id)?;
+            let parent_nft = Account::unpack(&parent_nft_account.data.borrow())?;
+            if parent_nft.mint != parent_record.nft_mint
+                || &parent_nft.owner != accounts.name_owner.key
+                || parent_nft.amount != 1
+            {
+                msg!("+ The signer does not hold the NFT of the parent domain");
+                return Err(ProgramError::InvalidArgument);
+            }
+            parent_nft_record_nonce = Some(parent_record.nonce);
+        }
+        None => {
+            msg!("+ The signer does not own the domain");
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+    let name = metadata_name(&name);
 
     // Verify metadata PDA
-    let (metadata_key, _) = Metadata::find_pda(&mint);
-    check_account_key(accounts.metadata_account, &metadata_key)?;
+    check_account_key(accounts.metadata_account, &nft_keys.metadata)?;
 
     // Verify edition PDA
-    let (collection_mint, _) =
-        Pubkey::find_program_address(&[COLLECTION_PREFIX, &program_id.to_bytes()], program_id);
-    check_account_key(accounts.collection_mint, &collection_mint)?;
+    check_account_key(accounts.collection_mint, &collection.mint)?;
 
-    let (edition_key, _) = MasterEdition::find_pda(&collection_mint);
+    let edition_key = MasterEdition::create_pda(collection.mint, collection.edition_nonce)?;
     check_account_key(accounts.edition_account, &edition_key)?;
 
     // Verify collection metadata PDA
-    let (collection_metadata, _) = Metadata::find_pda(&collection_mint);
+    let collection_metadata = Metadata::create_pda(collection.mint, collection.metadata_nonce)?;
     check_account_key(accounts.collection_metadata, &collection_metadata)?;
 
+    check_creator_share(program_id, accounts.central_state, creator_share)?;
+
+    // Collect protocol fee
+    let (fees, fee_vault_nonces) = get_fees(program_id, accounts.central_state)?;
+    collect_fee(
+        config,
+        &fees,
+        &fee_vault_nonces,
+        fees.tokenization_fee,
+        accounts.fee_payer,
+        accounts.fee_source,
+        accounts.fee_vault,
+        accounts.referrer,
+        accounts.system_program,
+        accounts.spl_token_program,
+    )?;
+
+    // Verify metadata URI
+    let uri = if uri.is_empty() {
+        check_account_owner(accounts.central_state, program_id)?;
+        let central_state = CentralState::from_account_info(accounts.central_state)?;
+        central_state.derive_uri(accounts.name_account.key, &mint)
+    } else {
+        match &config.metadata_signer {
+            Some(metadata_signer) if !accounts.metadata_signer.is_signer => {
+                check_metadata_attestation(
+                    accounts.instructions_sysvar,
+                    metadata_signer,
+                    accounts.name_account.key,
+                    &uri,
+                )?;
+            }
+            // Co-signed by the metadata signer, or no signer on this cluster
+            _ => (),
+        }
+        uri
+    };
+
+    // Verify NFT edition PDA
+    check_account_key(accounts.nft_edition, &nft_keys.edition)?;
+
+    // Verify escrow
+    check_account_key(accounts.nft_escrow, &nft_keys.escrow)?;
+
     // Verify mint
     let mint_info = Mint::unpack(&accounts.mint.data.borrow())?;
-    if mint_info.supply != 0 {
+    let has_edition = !accounts.nft_edition.data_is_empty();
+    if has_edition {
+        let escrow = Account::unpack(&accounts.nft_escrow.data.borrow())?;
+        if escrow.amount != 1 {
+            msg!("Expected escrow amount == 1 and received {}", escrow.amount);
+            return Err(ProgramError::InvalidAccountData);
+        }
+    } else if mint_info.supply != 0 {
         msg!("Expected supply == 0 and received {}", mint_info.supply);
         return Err(ProgramError::InvalidAccountData);
     }
 
     if accounts.nft_record.data_is_empty() {
-        msg!("+ Creating NFT record");
+        verbose_msg!("+ Creating NFT record");
         let nft_record = NftRecord::new(
             nft_record_nonce,
             *accounts.name_owner.key,
             *accounts.name_account.key,
             mint,
+            header.class,
+            soulbound,
+            nft_keys.nonces,
         );
         let seeds: &[&[u8]] = &[
             NftRecord::SEED,
@@ -235,50 +648,83 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) ->
             nft_record.borsh_len(),
         )?;
 
-        nft_record.save(&mut accounts.nft_record.data.borrow_mut());
+        nft_record.save(&mut accounts.nft_record.data.borrow_mut())?;
     } else {
-        msg!("+ NFT record already exists");
+        verbose_msg!("+ NFT record already exists");
         let mut nft_record =
             NftRecord::from_account_info(accounts.nft_record, Tag::InactiveRecord)?;
 
-        check_account_key(accounts.mint, &nft_record.nft_mint)?;
-
         nft_record.tag = Tag::ActiveRecord;
         nft_record.owner = *accounts.name_owner.key;
-
-        nft_record.save(&mut accounts.nft_record.data.borrow_mut());
+        nft_record.class = header.class;
+        nft_record.soulbound = soulbound;
+        nft_record.nonces = nft_keys.nonces;
+
+        if accounts.nft_record.data_len() < NftRecord::LEN {
+            Cpi::resize_account(
+                accounts.system_program,
+                accounts.fee_payer,
+                accounts.nft_record,
+                NftRecord::LEN,
+            )?;
+        }
+
+        nft_record.save(&mut accounts.nft_record.data.borrow_mut())?;
     }
 
-    // Mint token
-    let ix = mint_to(
-        &spl_token::ID,
-        &mint,
-        accounts.nft_destination.key,
-        &crate::central_state::KEY,
-        &[],
-        1,
-    )?;
-    let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[crate::central_state::NONCE]];
-
-    invoke_signed(
-        &ix,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.mint.clone(),
-            accounts.nft_destination.clone(),
-            accounts.central_state.clone(),
-        ],
-        &[seeds],
-    )?;
+    let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[config.central_state_nonce]];
+
+    if has_edition {
+        // The mint authority belongs to the master edition, the NFT is released from escrow
+        verbose_msg!("+ Releasing NFT from escrow");
+        let ix = spl_token::instruction::transfer(
+            &spl_token::ID,
+            &nft_keys.escrow,
+            accounts.nft_destination.key,
+            &config.central_state,
+            &[],
+            1,
+        )?;
+        invoke_signed(
+            &ix,
+            &[
+                accounts.spl_token_program.clone(),
+                accounts.nft_escrow.clone(),
+                accounts.nft_destination.clone(),
+                accounts.central_state.clone(),
+            ],
+            &[seeds],
+        )?;
+    } else {
+        // Mint token
+        let ix = mint_to(
+            &spl_token::ID,
+            &mint,
+            accounts.nft_destination.key,
+            &config.central_state,
+            &[],
+            1,
+        )?;
+        invoke_signed(
+            &ix,
+            &[
+                accounts.spl_token_program.clone(),
+                accounts.mint.clone(),
+                accounts.nft_destination.clone(),
+                accounts.central_state.clone(),
+            ],
+            &[seeds],
+        )?;
+    }
 
     // Create metadata
-    let central_creator = Creator {
-        address: crate::central_state::KEY,
-        verified: true,
-        share: 0,
-    };
+    let creators = nft_creators(
+        &config.central_state,
+        accounts.name_owner.key,
+        creator_share,
+    );
     if accounts.metadata_account.data_is_empty() {
-        msg!("+ Creating metadata");
+        verbose_msg!("+ Creating metadata");
         CreateMetadataAccountV3Cpi::new(
             accounts.metadata_program,
             CreateMetadataAccountV3CpiAccounts {
@@ -293,10 +739,10 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) ->
             CreateMetadataAccountV3InstructionArgs {
                 data: DataV2 {
                     name,
-                    symbol: META_SYMBOL.to_string(),
+                    symbol: collection.symbol.clone(),
                     uri,
                     seller_fee_basis_points: SELLER_BASIS,
-                    creators: Some(vec![central_creator, CREATOR_FEE]),
+                    creators: Some(creators),
                     collection: None,
                     uses: None,
                 },
@@ -306,7 +752,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) ->
         )
         .invoke_signed(&[seeds])?;
     } else {
-        msg!("+ Metadata already exists");
+        verbose_msg!("+ Metadata already exists");
         // Unverify collection first
         UnverifyCollectionCpi::new(
             accounts.metadata_program,
@@ -323,10 +769,10 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) ->
 
         let data = DataV2 {
             name,
-            symbol: META_SYMBOL.to_string(),
+            symbol: collection.symbol,
             uri,
             seller_fee_basis_points: SELLER_BASIS,
-            creators: Some(vec![central_creator, CREATOR_FEE]),
+            creators: Some(creators),
             collection: None,
             uses: None,
         };
@@ -338,7 +784,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) ->
             },
             UpdateMetadataAccountV2InstructionArgs {
                 data: Some(data),
-                new_update_authority: Some(crate::central_state::KEY),
+                new_update_authority: Some(config.central_state),
                 primary_sale_happened: None,
                 is_mutable: None,
             },
@@ -346,7 +792,90 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) ->
         .invoke_signed(&[seeds])?;
     }
 
-    msg!("+ Verifying collection");
+    if !has_edition {
+        // Create master edition
+        // (transfers the mint and freeze authorities to the edition PDA)
+        verbose_msg!("+ Creating master edition");
+        CreateMasterEditionV3Cpi::new(
+            accounts.metadata_program,
+            CreateMasterEditionV3CpiAccounts {
+                edition: accounts.nft_edition,
+                mint: accounts.mint,
+                update_authority: accounts.central_state,
+                mint_authority: accounts.central_state,
+                payer: accounts.fee_payer,
+                metadata: accounts.metadata_account,
+                token_program: accounts.spl_token_program,
+                system_program: accounts.system_program,
+                rent: Some(accounts.rent_account),
+            },
+            CreateMasterEditionV3InstructionArgs {
+                max_supply: Some(0),
+            },
+        )
+        .invoke_signed(&[seeds])?;
+
+        // Create escrow used on redemption
+        verbose_msg!("+ Creating NFT escrow");
+        let ix = create_associated_token_account_idempotent(
+            accounts.fee_payer.key,
+            &config.central_state,
+            &mint,
+            &spl_token::ID,
+        );
+        invoke(
+            &ix,
+            &[
+                accounts.ata_program.clone(),
+                accounts.fee_payer.clone(),
+                accounts.nft_escrow.clone(),
+                accounts.central_state.clone(),
+                accounts.mint.clone(),
+                accounts.system_program.clone(),
+                accounts.spl_token_program.clone(),
+            ],
+        )?;
+    }
+
+    if soulbound {
+        // The central state is approved as delegate so that only the program can thaw the NFT
+        verbose_msg!("+ Freezing soulbound NFT");
+        let destination = Account::unpack(&accounts.nft_destination.data.borrow())?;
+        if &destination.owner != accounts.name_owner.key {
+            msg!("+ Soulbound NFTs must be minted to the name owner");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let ix = approve(
+            &spl_token::ID,
+            accounts.nft_destination.key,
+            &config.central_state,
+            accounts.name_owner.key,
+            &[],
+            1,
+        )?;
+        invoke(
+            &ix,
+            &[
+                accounts.spl_token_program.clone(),
+                accounts.nft_destination.clone(),
+                accounts.central_state.clone(),
+                accounts.name_owner.clone(),
+            ],
+        )?;
+        FreezeDelegatedAccountCpi::new(
+            accounts.metadata_program,
+            FreezeDelegatedAccountCpiAccounts {
+                delegate: accounts.central_state,
+                token_account: accounts.nft_destination,
+                edition: accounts.nft_edition,
+                mint: accounts.mint,
+                token_program: accounts.spl_token_program,
+            },
+        )
+        .invoke_signed(&[seeds])?;
+    }
+
+    verbose_msg!("+ Verifying collection");
     SetAndVerifyCollectionCpi::new(
         accounts.metadata_program,
         SetAndVerifyCollectionCpiAccounts {
@@ -362,23 +891,62 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) ->
     )
     .invoke_signed(&[seeds])?;
 
-    // Transfer domain
-    let ix = transfer(
+    // Transfer domain, as the owner of the parent domain if the signer does not own it
+    let parent = parent.filter(|_| &header.owner != accounts.name_owner.key);
+    let authority = parent
+        .as_ref()
+        .map_or(*accounts.name_owner.key, |(_, parent_header)| {
+            parent_header.owner
+        });
+    let mut ix = transfer(
         spl_name_service::ID,
-        nft_record_key,
+        *accounts.nft_record.key,
         *accounts.name_account.key,
-        *accounts.name_owner.key,
-        None,
-    )?;
-    invoke(
-        &ix,
-        &[
-            accounts.spl_name_service_program.clone(),
-            accounts.nft_record.clone(),
-            accounts.name_account.clone(),
-            accounts.name_owner.clone(),
-        ],
+        authority,
+        class,
     )?;
+    let mut account_infos = vec![
+        accounts.spl_name_service_program.clone(),
+        accounts.nft_record.clone(),
+        accounts.name_account.clone(),
+        accounts.name_owner.clone(),
+    ];
+    account_infos.extend(accounts.name_class.cloned());
+    if let Some((parent_name, _)) = parent {
+        // The name service reads the parent domain after the optional class
+        if class.is_none() {
+            ix.accounts
+                .push(solana_program::instruction::AccountMeta::new_readonly(
+                    *parent_name.key,
+                    false,
+                ));
+        }
+        ix.accounts
+            .push(solana_program::instruction::AccountMeta::new_readonly(
+                *parent_name.key,
+                false,
+            ));
+        account_infos.push(parent_name.clone());
+    }
+    match (parent_nft_record_nonce, accounts.parent_nft_record) {
+        (Some(nonce), Some(parent_nft_record)) => {
+            account_infos.push(parent_nft_record.clone());
+            let parent_name_key = header.parent_name.to_bytes();
+            let seeds: &[&[u8]] = &[NftRecord::SEED, &parent_name_key, &[nonce]];
+            invoke_signed(&ix, &account_infos, &[seeds])?;
+        }
+        _ => invoke(&ix, &account_infos)?,
+    }
+
+    if wipe_data {
+        wipe_name_data(
+            accounts.spl_name_service_program,
+            accounts.name_account,
+            accounts.nft_record,
+            nft_record_nonce,
+            accounts.name_class.filter(|_| class.is_some()),
+        )?;
+    }
 
     Ok(())
 }
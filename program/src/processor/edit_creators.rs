@@ -0,0 +1,158 @@
+//! Edit the royalty split of a tokenized domain name
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    mpl_token_metadata::{
+        accounts::Metadata,
+        instructions::{
+            UpdateMetadataAccountV2Cpi, UpdateMetadataAccountV2CpiAccounts,
+            UpdateMetadataAccountV2InstructionArgs,
+        },
+        types::DataV2,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+};
+
+use crate::{
+    config::Config,
+    state::{NftRecordMut, Tag},
+    utils::{check_creator_share, nft_creators, nft_metadata_key},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The creator receiving a share of the royalties
+    pub creator: Pubkey,
+    /// Share of the royalties (in percent) of `creator`, 0 removes the creator
+    pub share: u8,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The NFT owner account
+    #[cons(signer)]
+    pub nft_owner: &'a T,
+
+    /// The NFT account
+    pub nft_account: &'a T,
+
+    /// The NFT record account
+    pub nft_record: &'a T,
+
+    /// The domain name account
+    pub name_account: &'a T,
+
+    /// The metadata account
+    #[cons(writable)]
+    pub metadata_account: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The metadata program account
+    pub metadata_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            nft_owner: next_account_info(accounts_iter)?,
+            nft_account: next_account_info(accounts_iter)?,
+            nft_record: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            metadata_account: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            metadata_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
+        check_account_key(accounts.metadata_program, &mpl_token_metadata::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.nft_account, &spl_token::ID)?;
+        check_account_owner(accounts.nft_record, program_id)?;
+        check_account_owner(accounts.name_account, &spl_name_service::ID)?;
+        check_account_owner(accounts.metadata_account, &mpl_token_metadata::ID)?;
+
+        // Check signer
+        check_signer(accounts.nft_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
+    let Params { creator, share } = params;
+
+    let (nft_mint, nonces) = {
+        let nft_record = NftRecordMut::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+        nft_record.check_key(accounts.nft_record, accounts.name_account.key, program_id)?;
+        (nft_record.base.nft_mint, nft_record.nonces())
+    };
+    let nft = Account::unpack(&accounts.nft_account.data.borrow())?;
+
+    if nft.mint != nft_mint {
+        msg!("+ NFT mint mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if nft.amount != 1 {
+        msg!("+ Invalid NFT amount, received {}", nft.amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+    check_account_key(accounts.nft_owner, &nft.owner)?;
+
+    let metadata_key = nft_metadata_key(&nft_mint, nonces)?;
+    check_account_key(accounts.metadata_account, &metadata_key)?;
+
+    check_creator_share(program_id, accounts.central_state, share)?;
+
+    let metadata = Metadata::safe_deserialize(&accounts.metadata_account.data.borrow())?;
+    let data = DataV2 {
+        name: metadata.name.trim_end_matches('\0').to_string(),
+        symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+        uri: metadata.uri.trim_end_matches('\0').to_string(),
+        seller_fee_basis_points: metadata.seller_fee_basis_points,
+        creators: Some(nft_creators(&config.central_state, &creator, share)),
+        collection: metadata.collection,
+        uses: metadata.uses,
+    };
+
+    verbose_msg!("+ Updating creators");
+    let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[config.central_state_nonce]];
+    UpdateMetadataAccountV2Cpi::new(
+        accounts.metadata_program,
+        UpdateMetadataAccountV2CpiAccounts {
+            metadata: accounts.metadata_account,
+            update_authority: accounts.central_state,
+        },
+        UpdateMetadataAccountV2InstructionArgs {
+            data: Some(data),
+            new_update_authority: None,
+            primary_sale_happened: None,
+            is_mutable: None,
+        },
+    )
+    .invoke_signed(&[seeds])?;
+
+    Ok(())
+}
@@ -9,17 +9,18 @@ use {
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
-        program::invoke_signed,
         program_error::ProgramError,
         pubkey::Pubkey,
     },
 };
 
 use solana_program::{msg, program_pack::Pack};
-use spl_name_service::instruction::update;
 use spl_token::state::Account;
 
-use crate::state::{NftRecord, Tag};
+use crate::{
+    state::{NftRecordMut, Tag},
+    utils::{check_name_class, next_optional_account_info, write_name_data},
+};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
@@ -50,6 +51,10 @@ pub struct Accounts<'a, T> {
 
     /// The SPL name service program account
     pub spl_name_service_program: &'a T,
+
+    /// The class of the domain name (omitted if the domain has no class)
+    #[cons(signer)]
+    pub name_class: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -65,6 +70,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             name_account: next_account_info(accounts_iter)?,
             spl_token_program: next_account_info(accounts_iter)?,
             spl_name_service_program: next_account_info(accounts_iter)?,
+            name_class: next_optional_account_info(accounts_iter, program_id),
         };
 
         // Check keys
@@ -86,13 +92,18 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
 
-    let (nft_record_key, _) = NftRecord::find_key(accounts.name_account.key, program_id);
-    check_account_key(accounts.nft_record, &nft_record_key)?;
-
-    let nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+    let (nft_record_nonce, nft_mint, class) = {
+        let nft_record = NftRecordMut::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+        nft_record.check_key(accounts.nft_record, accounts.name_account.key, program_id)?;
+        (
+            nft_record.base.nonce,
+            nft_record.base.nft_mint,
+            nft_record.class(),
+        )
+    };
     let nft = Account::unpack(&accounts.nft_account.data.borrow())?;
 
-    if nft.mint != nft_record.nft_mint {
+    if nft.mint != nft_mint {
         msg!("+ NFT mint mismatch");
         return Err(ProgramError::InvalidArgument);
     }
@@ -102,28 +113,14 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) ->
     }
     check_account_key(accounts.nft_owner, &nft.owner)?;
 
-    let ix = update(
-        spl_name_service::ID,
+    let class = check_name_class(&class, accounts.name_class)?;
+    write_name_data(
+        accounts.spl_name_service_program,
+        accounts.name_account,
+        accounts.nft_record,
+        nft_record_nonce,
+        accounts.name_class.filter(|_| class.is_some()),
         params.offset,
         params.data,
-        *accounts.name_account.key,
-        *accounts.nft_record.key,
-        None,
-    )?;
-    let seeds: &[&[u8]] = &[
-        NftRecord::SEED,
-        &accounts.name_account.key.to_bytes(),
-        &[nft_record.nonce],
-    ];
-    invoke_signed(
-        &ix,
-        &[
-            accounts.spl_name_service_program.clone(),
-            accounts.nft_record.clone(),
-            accounts.name_account.clone(),
-        ],
-        &[seeds],
-    )?;
-
-    Ok(())
+    )
 }
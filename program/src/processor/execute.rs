@@ -0,0 +1,148 @@
+//! Invoke an allowed program with the `NftRecord` of a tokenized domain name as signer
+//!
+//! The accounts of the invoked instruction follow the accounts below, in the order expected by
+//! the invoked program. The `NftRecord` signs wherever it appears among them.
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke_signed,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+};
+
+use crate::{
+    config::Config,
+    state::{CentralState, NftRecord, Tag},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The instruction data of the invoked program
+    pub data: Vec<u8>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The NFT owner account
+    #[cons(signer)]
+    pub nft_owner: &'a T,
+
+    /// The NFT account
+    pub nft_account: &'a T,
+
+    /// The NFT record account
+    pub nft_record: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The invoked program
+    pub target_program: &'a T,
+}
+
+/// Number of accounts preceding the accounts of the invoked instruction
+pub const ACCOUNTS_LEN: usize = 5;
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            nft_owner: next_account_info(accounts_iter)?,
+            nft_account: next_account_info(accounts_iter)?,
+            nft_record: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            target_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
+
+        // Check owners
+        check_account_owner(accounts.nft_account, &spl_token::ID)?;
+        check_account_owner(accounts.nft_record, program_id)?;
+        check_account_owner(accounts.central_state, program_id)?;
+
+        // Check signer
+        check_signer(accounts.nft_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let remaining_accounts = accounts.get(ACCOUNTS_LEN..).unwrap_or_default();
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+    nft_record.check_key(accounts.nft_record, &nft_record.name_account, program_id)?;
+    let nft = Account::unpack(&accounts.nft_account.data.borrow())?;
+
+    if nft.mint != nft_record.nft_mint {
+        msg!("+ NFT mint mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if nft.amount != 1 {
+        msg!("+ Invalid NFT amount, received {}", nft.amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+    check_account_key(accounts.nft_owner, &nft.owner)?;
+
+    // The name service is never allowed, the domain must be redeemed to leave the record
+    let central_state = CentralState::from_account_info(accounts.central_state)?;
+    let target = accounts.target_program.key;
+    if target == program_id
+        || target == &spl_name_service::ID
+        || !central_state.allowed_programs.contains(target)
+    {
+        msg!("+ Program {} is not allowed", target);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // The record signs nested calls as well, so the domain account cannot be passed at all
+    if remaining_accounts
+        .iter()
+        .any(|a| a.key == &nft_record.name_account)
+    {
+        msg!("+ The domain account cannot be passed to the invoked program");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let ix = solana_program::instruction::Instruction {
+        program_id: *target,
+        accounts: remaining_accounts
+            .iter()
+            .map(|a| solana_program::instruction::AccountMeta {
+                pubkey: *a.key,
+                is_signer: a.is_signer || a.key == accounts.nft_record.key,
+                is_writable: a.is_writable,
+            })
+            .collect(),
+        data: params.data,
+    };
+    let mut account_infos = vec![accounts.target_program.clone()];
+    account_infos.extend(remaining_accounts.iter().cloned());
+    let seeds: &[&[u8]] = &[
+        NftRecord::SEED,
+        &nft_record.name_account.to_bytes(),
+        &[nft_record.nonce],
+    ];
+    verbose_msg!("+ Invoking {}", target);
+    invoke_signed(&ix, &account_infos, &[seeds])?;
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! Return the effective owner of a domain name, i.e the NFT holder when the domain is tokenized
+//!
+//! The result is returned as a borsh serialized `EffectiveOwner` through the return data.
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::set_return_data,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    spl_name_service::state::NameRecordHeader,
+    spl_token::state::Account,
+};
+
+use crate::{
+    state::{NftRecord, Tag},
+    utils::next_optional_account_info,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+/// The return data of the instruction
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq)]
+pub struct EffectiveOwner {
+    /// The NFT holder if the domain is tokenized, the owner of the name account otherwise
+    pub owner: Pubkey,
+
+    /// Whether the domain is held by its NFT record
+    pub tokenized: bool,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The domain name account
+    pub name_account: &'a T,
+
+    /// The NFT record account of the domain (which may not exist)
+    pub nft_record: &'a T,
+
+    /// The token account holding the NFT (omitted if the domain is not tokenized)
+    pub nft_account: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            name_account: next_account_info(accounts_iter)?,
+            nft_record: next_account_info(accounts_iter)?,
+            nft_account: next_optional_account_info(accounts_iter, program_id),
+        };
+
+        // Check keys, a domain owned by any other account is not tokenized
+        let (nft_record_key, _) = NftRecord::find_key(accounts.name_account.key, program_id);
+        check_account_key(accounts.nft_record, &nft_record_key)?;
+
+        // Check owners
+        check_account_owner(accounts.name_account, &spl_name_service::ID)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let header = NameRecordHeader::unpack_from_slice(&accounts.name_account.data.borrow())?;
+    let effective_owner = if &header.owner != accounts.nft_record.key {
+        EffectiveOwner {
+            owner: header.owner,
+            tokenized: false,
+        }
+    } else {
+        check_account_owner(accounts.nft_record, program_id)?;
+        let nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+        let nft_account = accounts.nft_account.ok_or_else(|| {
+            msg!("+ The domain is tokenized, the token account holding the NFT is required");
+            ProgramError::NotEnoughAccountKeys
+        })?;
+        check_account_owner(nft_account, &spl_token::ID)?;
+        let nft = Account::unpack(&nft_account.data.borrow())?;
+        if nft.mint != nft_record.nft_mint {
+            msg!("+ NFT mint mismatch");
+            return Err(ProgramError::InvalidArgument);
+        }
+        if nft.amount != 1 {
+            msg!("+ Invalid NFT amount, received {}", nft.amount);
+            return Err(ProgramError::InvalidArgument);
+        }
+        EffectiveOwner {
+            owner: nft.owner,
+            tokenized: true,
+        }
+    };
+
+    verbose_msg!("+ Effective owner {}", effective_owner.owner);
+    set_return_data(&effective_owner.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+//! Resize and edit several ranges of the data registry of a tokenized domain name
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke_signed,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    spl_name_service::instruction::realloc,
+    spl_token::state::Account,
+};
+
+use crate::{
+    state::{NftRecord, Tag},
+    utils::{check_name_class, check_name_data_range, next_optional_account_info, write_name_data},
+};
+
+/// Bytes written at `offset` into the domain name registry
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct DataWrite {
+    /// Offset of the first written byte
+    pub offset: u32,
+    /// The bytes to write (overwriting any previous data)
+    pub data: Vec<u8>,
+}
+
+/// A range of `len` bytes starting at `offset` in the domain name registry
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct DataRange {
+    /// Offset of the first byte of the range
+    pub offset: u32,
+    /// Number of bytes in the range
+    pub len: u32,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// New size of the registry (excluding the name record header), `None` keeps the current size
+    pub space: Option<u32>,
+    /// Ranges to zero, applied after the resize
+    pub clears: Vec<DataRange>,
+    /// Writes applied in order after the clears
+    pub writes: Vec<DataWrite>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The NFT owner account
+    #[cons(signer)]
+    pub nft_owner: &'a T,
+
+    /// The NFT account
+    pub nft_account: &'a T,
+
+    /// The NFT record account
+    pub nft_record: &'a T,
+
+    /// The domain name account
+    #[cons(writable)]
+    pub name_account: &'a T,
+
+    /// The SPL token program account
+    pub spl_token_program: &'a T,
+
+    /// The SPL name service program account
+    pub spl_name_service_program: &'a T,
+
+    /// The account paying for a larger registry (and refunded for a smaller one)
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The class of the domain name (omitted if the domain has no class)
+    #[cons(signer)]
+    pub name_class: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            nft_owner: next_account_info(accounts_iter)?,
+            nft_account: next_account_info(accounts_iter)?,
+            nft_record: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            spl_name_service_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            name_class: next_optional_account_info(accounts_iter, program_id),
+        };
+
+        // Check keys
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.nft_account, &spl_token::ID)?;
+        check_account_owner(accounts.nft_record, program_id)?;
+        check_account_owner(accounts.name_account, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.nft_owner)?;
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let Params {
+        space,
+        clears,
+        writes,
+    } = params;
+
+    let nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+    nft_record.check_key(accounts.nft_record, accounts.name_account.key, program_id)?;
+    let nft = Account::unpack(&accounts.nft_account.data.borrow())?;
+
+    if nft.mint != nft_record.nft_mint {
+        msg!("+ NFT mint mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if nft.amount != 1 {
+        msg!("+ Invalid NFT amount, received {}", nft.amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+    check_account_key(accounts.nft_owner, &nft.owner)?;
+
+    let class = check_name_class(&nft_record.class, accounts.name_class)?;
+    let name_class = accounts.name_class.filter(|_| class.is_some());
+    let seeds: &[&[u8]] = &[
+        NftRecord::SEED,
+        &accounts.name_account.key.to_bytes(),
+        &[nft_record.nonce],
+    ];
+
+    if let Some(space) = space {
+        verbose_msg!("+ Resizing domain registry to {} bytes", space);
+        let ix = realloc(
+            spl_name_service::ID,
+            *accounts.fee_payer.key,
+            *accounts.name_account.key,
+            *accounts.nft_record.key,
+            space,
+        )?;
+        invoke_signed(
+            &ix,
+            &[
+                accounts.spl_name_service_program.clone(),
+                accounts.system_program.clone(),
+                accounts.fee_payer.clone(),
+                accounts.name_account.clone(),
+                accounts.nft_record.clone(),
+            ],
+            &[seeds],
+        )?;
+    }
+
+    for DataRange { offset, len } in clears {
+        // Checked before the zeroes are allocated
+        check_name_data_range(accounts.name_account, offset, len as usize)?;
+        write_name_data(
+            accounts.spl_name_service_program,
+            accounts.name_account,
+            accounts.nft_record,
+            nft_record.nonce,
+            name_class,
+            offset,
+            vec![0; len as usize],
+        )?;
+    }
+
+    for DataWrite { offset, data } in writes {
+        write_name_data(
+            accounts.spl_name_service_program,
+            accounts.name_account,
+            accounts.nft_record,
+            nft_record.nonce,
+            name_class,
+            offset,
+            data,
+        )?;
+    }
+
+    Ok(())
+}
@@ -6,21 +6,38 @@ use {
         BorshSize, InstructionsAccount,
     },
     borsh::{BorshDeserialize, BorshSerialize},
+    mpl_token_metadata::instructions::{ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts},
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
+        msg,
         program::{invoke, invoke_signed},
         program_error::ProgramError,
+        program_pack::Pack,
         pubkey::Pubkey,
+        system_program,
     },
     spl_name_service::instruction::transfer,
-    spl_token::instruction::burn,
+    spl_token::{
+        instruction::{burn, revoke},
+        state::{Account, Mint},
+    },
 };
 
-use crate::state::{NftRecord, Tag, MINT_PREFIX};
+use crate::{
+    config::Config,
+    state::{NftRecord, NftRecordMut, Tag},
+    utils::{
+        check_name_class, collect_fee, get_fees, next_optional_account_info, nft_edition_key,
+        wipe_name_data,
+    },
+};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
-pub struct Params {}
+pub struct Params {
+    /// Zero the data of the name account before it is returned to the NFT owner
+    pub wipe_data: bool,
+}
 
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
@@ -49,6 +66,39 @@ pub struct Accounts<'a, T> {
 
     /// The SPL name service program account
     pub spl_name_service_program: &'a T,
+
+    /// The central state escrow of the NFT
+    #[cons(writable)]
+    pub nft_escrow: &'a T,
+
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The account paying the protocol fee (the NFT owner for SOL fees, or its token account)
+    #[cons(writable)]
+    pub fee_source: &'a T,
+
+    /// The fee vault of the central state
+    #[cons(writable)]
+    pub fee_vault: &'a T,
+
+    /// The referrer receiving a share of the protocol fee (the fee vault if there is none)
+    #[cons(writable)]
+    pub referrer: &'a T,
+
+    /// The master edition account of the NFT
+    pub nft_edition: &'a T,
+
+    /// The metadata program account
+    pub metadata_program: &'a T,
+
+    /// The class of the domain name (omitted if the domain has no class)
+    #[cons(signer)]
+    pub name_class: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -65,17 +115,32 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             name_account: next_account_info(accounts_iter)?,
             spl_token_program: next_account_info(accounts_iter)?,
             spl_name_service_program: next_account_info(accounts_iter)?,
+            nft_escrow: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            fee_source: next_account_info(accounts_iter)?,
+            fee_vault: next_account_info(accounts_iter)?,
+            referrer: next_account_info(accounts_iter)?,
+            nft_edition: next_account_info(accounts_iter)?,
+            metadata_program: next_account_info(accounts_iter)?,
+            name_class: next_optional_account_info(accounts_iter, program_id),
         };
 
         // Check keys
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
         check_account_key(accounts.spl_token_program, &spl_token::ID)?;
         check_account_key(accounts.spl_name_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.metadata_program, &mpl_token_metadata::ID)?;
 
         // Check owners
         check_account_owner(accounts.mint, &spl_token::ID)?;
         check_account_owner(accounts.nft_source, &spl_token::ID)?;
         check_account_owner(accounts.nft_record, program_id)?;
         check_account_owner(accounts.name_account, &spl_name_service::ID)?;
+        check_account_owner(accounts.nft_escrow, &spl_token::ID)
+            .or_else(|_| check_account_owner(accounts.nft_escrow, &system_program::ID))?;
 
         // Check signer
         check_signer(accounts.nft_owner)?;
@@ -83,67 +148,166 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         Ok(accounts)
     }
 }
-pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
-    let mut nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+    let Params { wipe_data } = params;
+    let config = Config::from_program_id(program_id)?;
+    let (nft_record_nonce, nft_mint, name_class, soulbound, nonces) = {
+        let nft_record = NftRecordMut::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+        nft_record.check_key(accounts.nft_record, accounts.name_account.key, program_id)?;
+        (
+            nft_record.base.nonce,
+            nft_record.base.nft_mint,
+            nft_record.class(),
+            nft_record.soulbound(),
+            nft_record.nonces(),
+        )
+    };
+    check_account_key(accounts.mint, &nft_mint)?;
+
+    // Soulbound NFTs are thawed below, any other frozen NFT is locked by its lock authority
+    let nft = Account::unpack(&accounts.nft_source.data.borrow())?;
+    if nft.is_frozen() && !soulbound {
+        msg!("+ The NFT is locked");
+        return Err(ProgramError::InvalidArgument);
+    }
 
-    let (nft_record_key, _) = NftRecord::find_key(accounts.name_account.key, program_id);
-    check_account_key(accounts.nft_record, &nft_record_key)?;
+    let mint_info = Mint::unpack(&accounts.mint.data.borrow())?;
+    if mint_info.mint_authority.contains(&config.central_state) {
+        // Burn NFT (mint created before master editions were introduced)
+        let ix = burn(
+            &spl_token::ID,
+            accounts.nft_source.key,
+            &nft_mint,
+            accounts.nft_owner.key,
+            &[],
+            1,
+        )?;
+        invoke(
+            &ix,
+            &[
+                accounts.spl_token_program.clone(),
+                accounts.nft_source.clone(),
+                accounts.mint.clone(),
+                accounts.nft_owner.clone(),
+            ],
+        )?;
+    } else {
+        if soulbound {
+            verbose_msg!("+ Thawing soulbound NFT");
+            let nft_edition_key = nft_edition_key(&nft_mint, nonces)?;
+            check_account_key(accounts.nft_edition, &nft_edition_key)?;
+            let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[config.central_state_nonce]];
+            ThawDelegatedAccountCpi::new(
+                accounts.metadata_program,
+                ThawDelegatedAccountCpiAccounts {
+                    delegate: accounts.central_state,
+                    token_account: accounts.nft_source,
+                    edition: accounts.nft_edition,
+                    mint: accounts.mint,
+                    token_program: accounts.spl_token_program,
+                },
+            )
+            .invoke_signed(&[seeds])?;
+        }
 
-    let (mint, _) = Pubkey::find_program_address(
-        &[MINT_PREFIX, &accounts.name_account.key.to_bytes()],
-        program_id,
-    );
-    check_account_key(accounts.mint, &mint)?;
+        // The supply is capped by the master edition, the NFT is kept in escrow
+        verbose_msg!("+ Transferring NFT to escrow");
+        let nft_escrow_key = match nonces {
+            Some(nonces) => config.nft_escrow_key(&nft_mint, nonces.escrow)?,
+            None => config.find_nft_escrow_key(&nft_mint).0,
+        };
+        check_account_key(accounts.nft_escrow, &nft_escrow_key)?;
+        let ix = spl_token::instruction::transfer(
+            &spl_token::ID,
+            accounts.nft_source.key,
+            accounts.nft_escrow.key,
+            accounts.nft_owner.key,
+            &[],
+            1,
+        )?;
+        invoke(
+            &ix,
+            &[
+                accounts.spl_token_program.clone(),
+                accounts.nft_source.clone(),
+                accounts.nft_escrow.clone(),
+                accounts.nft_owner.clone(),
+            ],
+        )?;
+    }
 
-    // Burn NFT
-    let ix = burn(
+    // Revoke the delegation of the central state, a lock record of the token account must not
+    // outlive the tokenization
+    let ix = revoke(
         &spl_token::ID,
         accounts.nft_source.key,
-        &nft_record.nft_mint,
         accounts.nft_owner.key,
         &[],
-        1,
     )?;
     invoke(
         &ix,
         &[
             accounts.spl_token_program.clone(),
             accounts.nft_source.clone(),
-            accounts.mint.clone(),
             accounts.nft_owner.clone(),
         ],
     )?;
 
+    // Collect protocol fee
+    let (fees, fee_vault_nonces) = get_fees(program_id, accounts.central_state)?;
+    collect_fee(
+        config,
+        &fees,
+        &fee_vault_nonces,
+        fees.redemption_fee,
+        accounts.nft_owner,
+        accounts.fee_source,
+        accounts.fee_vault,
+        accounts.referrer,
+        accounts.system_program,
+        accounts.spl_token_program,
+    )?;
+
     // Transfer domain
+    let class = check_name_class(&name_class, accounts.name_class)?;
+    if wipe_data {
+        wipe_name_data(
+            accounts.spl_name_service_program,
+            accounts.name_account,
+            accounts.nft_record,
+            nft_record_nonce,
+            accounts.name_class.filter(|_| class.is_some()),
+        )?;
+    }
     let ix = transfer(
         spl_name_service::ID,
         *accounts.nft_owner.key,
         *accounts.name_account.key,
         *accounts.nft_record.key,
-        None,
+        class,
     )?;
+    let mut account_infos = vec![
+        accounts.spl_name_service_program.clone(),
+        accounts.nft_owner.clone(),
+        accounts.name_account.clone(),
+        accounts.nft_record.clone(),
+    ];
+    account_infos.extend(accounts.name_class.cloned());
     let seeds: &[&[u8]] = &[
         NftRecord::SEED,
         &accounts.name_account.key.to_bytes(),
-        &[nft_record.nonce],
+        &[nft_record_nonce],
     ];
-    invoke_signed(
-        &ix,
-        &[
-            accounts.spl_name_service_program.clone(),
-            accounts.nft_owner.clone(),
-            accounts.name_account.clone(),
-            accounts.nft_record.clone(),
-        ],
-        &[seeds],
-    )?;
+    invoke_signed(&ix, &account_infos, &[seeds])?;
 
     // Update NFT record
-    nft_record.tag = Tag::InactiveRecord;
-    nft_record.owner = *accounts.nft_owner.key;
-
-    nft_record.save(&mut accounts.nft_record.data.borrow_mut());
+    let mut nft_record = NftRecordMut::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+    nft_record.base.tag = Tag::InactiveRecord as u8;
+    nft_record.base.owner = *accounts.nft_owner.key;
+    if let Some(extension) = nft_record.extension.as_mut() {
+        extension.soulbound = 0;
+    }
 
     Ok(())
 }
@@ -0,0 +1,148 @@
+//! Register a new TLD that can be tokenized
+
+use crate::{config::Config, cpi::Cpi, state::TldRecord, utils::check_admin};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    mpl_token_metadata::{
+        accounts::{MasterEdition, Metadata},
+        MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The name of the TLD collection
+    pub collection_name: String,
+
+    /// The URI of the TLD collection metadata
+    pub collection_uri: String,
+
+    /// The symbol of the NFTs (e.g `.sol`)
+    pub symbol: String,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The TLD record account
+    #[cons(writable)]
+    pub tld_record: &'a T,
+
+    /// The parent name account of the TLD
+    pub tld: &'a T,
+
+    /// The program data account of the tokenizer
+    pub program_data: &'a T,
+
+    /// The upgrade authority of the tokenizer
+    #[cons(signer)]
+    pub admin: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            tld_record: next_account_info(accounts_iter)?,
+            tld: next_account_info(accounts_iter)?,
+            program_data: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.tld_record, &system_program::ID)?;
+        check_account_owner(accounts.tld, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_admin(program_id, accounts.program_data, accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
+    let Params {
+        collection_name,
+        collection_uri,
+        symbol,
+    } = params;
+
+    if accounts.tld.key == &config.root_domain {
+        msg!("+ The .sol TLD is already supported");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if collection_name.len() > MAX_NAME_LENGTH
+        || collection_uri.len() > MAX_URI_LENGTH
+        || symbol.len() > MAX_SYMBOL_LENGTH
+    {
+        msg!("+ Collection metadata exceeds the Metaplex limits");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (tld_record_key, tld_record_nonce) = TldRecord::find_key(accounts.tld.key, program_id);
+    check_account_key(accounts.tld_record, &tld_record_key)?;
+
+    let (collection_mint, collection_nonce) =
+        TldRecord::find_collection_key(accounts.tld.key, program_id);
+    let (_, collection_metadata_nonce) = Metadata::find_pda(&collection_mint);
+    let (_, collection_edition_nonce) = MasterEdition::find_pda(&collection_mint);
+
+    verbose_msg!("+ Creating TLD record");
+    let tld_record = TldRecord::new(
+        tld_record_nonce,
+        collection_nonce,
+        collection_metadata_nonce,
+        collection_edition_nonce,
+        *accounts.tld.key,
+        collection_name,
+        collection_uri,
+        symbol,
+    );
+    let seeds: &[&[u8]] = &[
+        TldRecord::SEED,
+        &accounts.tld.key.to_bytes(),
+        &[tld_record_nonce],
+    ];
+    Cpi::create_account(
+        program_id,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.tld_record,
+        seeds,
+        tld_record.borsh_len(),
+    )?;
+
+    tld_record.save(&mut accounts.tld_record.data.borrow_mut())?;
+
+    Ok(())
+}
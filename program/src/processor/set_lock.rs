@@ -0,0 +1,222 @@
+//! Lock or unlock a domain NFT in the holder's wallet
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    mpl_token_metadata::instructions::{
+        FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts, ThawDelegatedAccountCpi,
+        ThawDelegatedAccountCpiAccounts,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke_signed,
+        program_error::ProgramError,
+        program_option::COption,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    spl_token::{
+        instruction::{freeze_account, thaw_account},
+        state::{Account, Mint},
+    },
+};
+
+use crate::{
+    config::Config,
+    state::{LockRecord, NftRecordMut, Tag},
+    utils::nft_edition_key,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// `true` to freeze the token account, `false` to thaw it
+    pub locked: bool,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The lock authority of the token account
+    #[cons(signer)]
+    pub lock_authority: &'a T,
+
+    /// The lock record of the token account
+    pub lock_record: &'a T,
+
+    /// The NFT record account
+    pub nft_record: &'a T,
+
+    /// The token account holding the NFT
+    #[cons(writable)]
+    pub nft_account: &'a T,
+
+    /// The mint of the NFT
+    pub mint: &'a T,
+
+    /// The master edition account of the NFT
+    pub nft_edition: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The SPL token program account
+    pub spl_token_program: &'a T,
+
+    /// The metadata program account
+    pub metadata_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            lock_authority: next_account_info(accounts_iter)?,
+            lock_record: next_account_info(accounts_iter)?,
+            nft_record: next_account_info(accounts_iter)?,
+            nft_account: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            nft_edition: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            metadata_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(accounts.metadata_program, &mpl_token_metadata::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.lock_record, program_id)?;
+        check_account_owner(accounts.nft_record, program_id)?;
+        check_account_owner(accounts.nft_account, &spl_token::ID)?;
+        check_account_owner(accounts.mint, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.lock_authority)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
+    let Params { locked } = params;
+
+    let lock_record = LockRecord::from_account_info(accounts.lock_record)?;
+    lock_record.check_key(accounts.lock_record, accounts.nft_account.key, program_id)?;
+    check_account_key(accounts.lock_authority, &lock_record.lock_authority)?;
+    check_account_key(accounts.mint, &lock_record.nft_mint)?;
+
+    // The record of a redeemed domain is inactive, and soulbound NFTs stay frozen
+    let nonces = {
+        let nft_record = NftRecordMut::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+        nft_record.check_key(
+            accounts.nft_record,
+            &nft_record.base.name_account,
+            program_id,
+        )?;
+        check_account_key(accounts.mint, &nft_record.base.nft_mint)?;
+        if nft_record.soulbound() {
+            msg!("+ Soulbound NFTs cannot be locked or unlocked");
+            return Err(ProgramError::InvalidArgument);
+        }
+        nft_record.nonces()
+    };
+
+    let nft = Account::unpack(&accounts.nft_account.data.borrow())?;
+    if nft.is_frozen() == locked {
+        msg!(
+            "+ The NFT is already {}",
+            if locked { "locked" } else { "unlocked" }
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+    if locked && nft.amount != 1 {
+        msg!("+ Invalid NFT amount, received {}", nft.amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+    // The delegation is revoked on redemption, which invalidates the lock record
+    if nft.delegate != COption::Some(config.central_state) {
+        msg!("+ The lock authority was revoked");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[config.central_state_nonce]];
+    let mint = Mint::unpack(&accounts.mint.data.borrow())?;
+    if mint.freeze_authority.contains(&config.central_state) {
+        // Mint created before master editions were introduced
+        let ix = if locked {
+            verbose_msg!("+ Freezing NFT");
+            freeze_account(
+                &spl_token::ID,
+                accounts.nft_account.key,
+                accounts.mint.key,
+                &config.central_state,
+                &[],
+            )?
+        } else {
+            verbose_msg!("+ Thawing NFT");
+            thaw_account(
+                &spl_token::ID,
+                accounts.nft_account.key,
+                accounts.mint.key,
+                &config.central_state,
+                &[],
+            )?
+        };
+        invoke_signed(
+            &ix,
+            &[
+                accounts.spl_token_program.clone(),
+                accounts.nft_account.clone(),
+                accounts.mint.clone(),
+                accounts.central_state.clone(),
+            ],
+            &[seeds],
+        )?;
+        return Ok(());
+    }
+
+    // The freeze authority belongs to the master edition, the central state acts as delegate
+    let nft_edition_key = nft_edition_key(&lock_record.nft_mint, nonces)?;
+    check_account_key(accounts.nft_edition, &nft_edition_key)?;
+    if locked {
+        verbose_msg!("+ Freezing NFT");
+        FreezeDelegatedAccountCpi::new(
+            accounts.metadata_program,
+            FreezeDelegatedAccountCpiAccounts {
+                delegate: accounts.central_state,
+                token_account: accounts.nft_account,
+                edition: accounts.nft_edition,
+                mint: accounts.mint,
+                token_program: accounts.spl_token_program,
+            },
+        )
+        .invoke_signed(&[seeds])?;
+    } else {
+        verbose_msg!("+ Thawing NFT");
+        ThawDelegatedAccountCpi::new(
+            accounts.metadata_program,
+            ThawDelegatedAccountCpiAccounts {
+                delegate: accounts.central_state,
+                token_account: accounts.nft_account,
+                edition: accounts.nft_edition,
+                mint: accounts.mint,
+                token_program: accounts.spl_token_program,
+            },
+        )
+        .invoke_signed(&[seeds])?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+//! Grant or revoke the authority allowed to lock a domain NFT in the holder's wallet
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+    spl_token::{
+        instruction::{approve, revoke},
+        state::Account,
+    },
+};
+
+use crate::{
+    config::Config,
+    cpi::Cpi,
+    state::{LockRecord, NftRecord, Tag},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The lock authority, the default key revokes the current one
+    pub lock_authority: Pubkey,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The NFT owner account
+    #[cons(signer)]
+    pub nft_owner: &'a T,
+
+    /// The token account holding the NFT
+    #[cons(writable)]
+    pub nft_account: &'a T,
+
+    /// The NFT record account
+    pub nft_record: &'a T,
+
+    /// The domain name account
+    pub name_account: &'a T,
+
+    /// The lock record of the token account
+    #[cons(writable)]
+    pub lock_record: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The SPL token program account
+    pub spl_token_program: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            nft_owner: next_account_info(accounts_iter)?,
+            nft_account: next_account_info(accounts_iter)?,
+            nft_record: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            lock_record: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.nft_account, &spl_token::ID)?;
+        check_account_owner(accounts.nft_record, program_id)?;
+        check_account_owner(accounts.name_account, &spl_name_service::ID)?;
+        check_account_owner(accounts.lock_record, &system_program::ID)
+            .or_else(|_| check_account_owner(accounts.lock_record, program_id))?;
+
+        // Check signer
+        check_signer(accounts.nft_owner)?;
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
+    let Params { lock_authority } = params;
+
+    let nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+    nft_record.check_key(accounts.nft_record, accounts.name_account.key, program_id)?;
+    let nft = Account::unpack(&accounts.nft_account.data.borrow())?;
+
+    if nft.mint != nft_record.nft_mint {
+        msg!("+ NFT mint mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if nft.amount != 1 {
+        msg!("+ Invalid NFT amount, received {}", nft.amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+    check_account_key(accounts.nft_owner, &nft.owner)?;
+
+    // A frozen account is either locked or soulbound
+    if nft.is_frozen() {
+        msg!("+ The NFT is locked");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (lock_record_key, lock_record_nonce) =
+        LockRecord::find_key(accounts.nft_account.key, program_id);
+    check_account_key(accounts.lock_record, &lock_record_key)?;
+
+    if lock_authority == Pubkey::default() {
+        verbose_msg!("+ Revoking lock authority");
+        LockRecord::from_account_info(accounts.lock_record)?;
+        let ix = revoke(
+            &spl_token::ID,
+            accounts.nft_account.key,
+            accounts.nft_owner.key,
+            &[],
+        )?;
+        invoke(
+            &ix,
+            &[
+                accounts.spl_token_program.clone(),
+                accounts.nft_account.clone(),
+                accounts.nft_owner.clone(),
+            ],
+        )?;
+
+        // Close the lock record
+        let lamports = accounts.lock_record.lamports();
+        **accounts.lock_record.try_borrow_mut_lamports()? = 0;
+        **accounts.fee_payer.try_borrow_mut_lamports()? += lamports;
+        accounts.lock_record.data.borrow_mut().fill(0);
+        return Ok(());
+    }
+
+    let lock_record = LockRecord::new(
+        lock_record_nonce,
+        *accounts.nft_account.key,
+        nft.mint,
+        lock_authority,
+    );
+
+    if accounts.lock_record.data_is_empty() {
+        verbose_msg!("+ Creating lock record");
+        let seeds: &[&[u8]] = &[
+            LockRecord::SEED,
+            &accounts.nft_account.key.to_bytes(),
+            &[lock_record_nonce],
+        ];
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.lock_record,
+            seeds,
+            lock_record.borsh_len(),
+        )?;
+    } else {
+        verbose_msg!("+ Updating lock authority");
+        LockRecord::from_account_info(accounts.lock_record)?;
+    }
+
+    lock_record.save(&mut accounts.lock_record.data.borrow_mut())?;
+
+    // The central state is approved as delegate so that it can freeze the NFT on behalf of the lock authority
+    let ix = approve(
+        &spl_token::ID,
+        accounts.nft_account.key,
+        &config.central_state,
+        accounts.nft_owner.key,
+        &[],
+        1,
+    )?;
+    invoke(
+        &ix,
+        &[
+            accounts.spl_token_program.clone(),
+            accounts.nft_account.clone(),
+            accounts.central_state.clone(),
+            accounts.nft_owner.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
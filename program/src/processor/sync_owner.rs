@@ -0,0 +1,72 @@
+//! Set the owner of an active NFT record to the current NFT holder
+
+use {
+    bonfida_utils::{checks::check_account_owner, BorshSize, InstructionsAccount},
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+};
+
+use crate::state::{NftRecord, Tag};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The NFT record account
+    #[cons(writable)]
+    pub nft_record: &'a T,
+
+    /// The token account holding the NFT
+    pub nft_account: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            nft_record: next_account_info(accounts_iter)?,
+            nft_account: next_account_info(accounts_iter)?,
+        };
+
+        // Check owners
+        check_account_owner(accounts.nft_record, program_id)?;
+        check_account_owner(accounts.nft_account, &spl_token::ID)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let mut nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveRecord)?;
+    nft_record.check_key(accounts.nft_record, &nft_record.name_account, program_id)?;
+
+    let nft = Account::unpack(&accounts.nft_account.data.borrow())?;
+    if nft.mint != nft_record.nft_mint {
+        msg!("+ NFT mint mismatch");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if nft.amount != 1 {
+        msg!("+ Invalid NFT amount, received {}", nft.amount);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    verbose_msg!("+ Setting record owner to {}", nft.owner);
+    nft_record.owner = nft.owner;
+    nft_record.save(&mut accounts.nft_record.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -5,7 +5,10 @@ use mpl_token_metadata::{
     instructions::{UnverifyCollectionCpi, UnverifyCollectionCpiAccounts},
 };
 
-use crate::state::{COLLECTION_PREFIX, METADATA_SIGNER};
+use crate::{
+    config::Config,
+    utils::{get_collection, next_optional_account_info},
+};
 
 use {
     bonfida_utils::{
@@ -57,14 +60,19 @@ pub struct Accounts<'a, T> {
     /// Rent sysvar account
     pub rent_account: &'a T,
 
-    /// The metadata signer
+    /// The metadata signer (any signer on clusters without one)
     #[cons(signer)]
-    #[cfg(not(feature = "devnet"))]
     pub metadata_signer: &'a T,
+
+    /// The TLD record account (omitted for .sol)
+    pub tld_record: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
-    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
             metadata_account: next_account_info(accounts_iter)?,
@@ -76,17 +84,19 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             metadata_program: next_account_info(accounts_iter)?,
             system_program: next_account_info(accounts_iter)?,
             rent_account: next_account_info(accounts_iter)?,
-            #[cfg(not(feature = "devnet"))]
             metadata_signer: next_account_info(accounts_iter)?,
+            tld_record: next_optional_account_info(accounts_iter, program_id),
         };
 
         // Check keys
-        check_account_key(accounts.central_state, &crate::central_state::KEY)?;
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
         check_account_key(accounts.metadata_program, &mpl_token_metadata::ID)?;
         check_account_key(accounts.system_program, &system_program::ID)?;
         check_account_key(accounts.rent_account, &sysvar::rent::ID)?;
-        #[cfg(not(feature = "devnet"))]
-        check_account_key(accounts.metadata_signer, &METADATA_SIGNER)?;
+        if let Some(metadata_signer) = &config.metadata_signer {
+            check_account_key(accounts.metadata_signer, metadata_signer)?;
+        }
 
         // Check owners
         check_account_owner(accounts.metadata_account, &mpl_token_metadata::ID)?;
@@ -94,7 +104,6 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         check_account_owner(accounts.collection_metadata, &mpl_token_metadata::ID)?;
         check_account_owner(accounts.collection_mint, &spl_token::ID)?;
 
-        #[cfg(not(feature = "devnet"))]
         check_signer(accounts.metadata_signer)?;
 
         Ok(accounts)
@@ -102,21 +111,21 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 }
 
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
-    let accounts = Accounts::parse(accounts)?;
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
 
     // Verify edition PDA
-    let (collection_mint, _) =
-        Pubkey::find_program_address(&[COLLECTION_PREFIX, &program_id.to_bytes()], program_id);
-    check_account_key(accounts.collection_mint, &collection_mint)?;
+    let collection = get_collection(program_id, accounts.tld_record)?;
+    check_account_key(accounts.collection_mint, &collection.mint)?;
 
-    let (edition_key, _) = MasterEdition::find_pda(&collection_mint);
+    let edition_key = MasterEdition::create_pda(collection.mint, collection.edition_nonce)?;
     check_account_key(accounts.edition_account, &edition_key)?;
 
     // Verify collection metadata PDA
-    let (collection_metadata, _) = Metadata::find_pda(&collection_mint);
+    let collection_metadata = Metadata::create_pda(collection.mint, collection.metadata_nonce)?;
     check_account_key(accounts.collection_metadata, &collection_metadata)?;
 
-    let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[crate::central_state::NONCE]];
+    let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[config.central_state_nonce]];
 
     UnverifyCollectionCpi::new(
         accounts.metadata_program,
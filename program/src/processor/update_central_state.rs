@@ -0,0 +1,159 @@
+//! Create or update the central state configuration
+
+use crate::{
+    config::Config,
+    cpi::Cpi,
+    state::{CentralState, Fees, UriDerivation},
+    utils::check_admin,
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    mpl_token_metadata::MAX_URI_LENGTH,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+/// Length of a base58 encoded account key appended to the base URI
+const MAX_KEY_LENGTH: usize = 44;
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// Base of the metadata URIs derived on-chain
+    pub base_uri: String,
+
+    /// Account key appended to `base_uri` when deriving a metadata URI
+    pub uri_derivation: UriDerivation,
+
+    /// Maximum share of the royalties (in percent) the tokenizer of a domain can receive
+    pub max_creator_share: u8,
+
+    /// Protocol fees collected on tokenization and redemption
+    pub fees: Fees,
+
+    /// Programs the NFT holder can invoke with the `NftRecord` as signer
+    pub allowed_programs: Vec<Pubkey>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The program data account of the tokenizer
+    pub program_data: &'a T,
+
+    /// The upgrade authority of the tokenizer
+    #[cons(signer)]
+    pub admin: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            program_data: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.central_state, &system_program::ID)
+            .or_else(|_| check_account_owner(accounts.central_state, program_id))?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_admin(program_id, accounts.program_data, accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
+    let Params {
+        base_uri,
+        uri_derivation,
+        max_creator_share,
+        fees,
+        allowed_programs,
+    } = params;
+
+    if base_uri.len() + MAX_KEY_LENGTH > MAX_URI_LENGTH {
+        msg!("+ Base URI is too long");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if max_creator_share > 100 || fees.referrer_share > 100 {
+        msg!("+ Shares must be percentages");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if allowed_programs.contains(program_id) || allowed_programs.contains(&spl_name_service::ID) {
+        msg!("+ The tokenizer and the name service cannot be allowed");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let central_state = CentralState::new(
+        base_uri,
+        uri_derivation,
+        max_creator_share,
+        fees,
+        config.find_fee_vault_nonces(&fees.mint),
+        allowed_programs,
+    );
+
+    if accounts.central_state.data_is_empty() {
+        verbose_msg!("+ Creating central state");
+        let seeds: &[&[u8]] = &[&program_id.to_bytes(), &[config.central_state_nonce]];
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.central_state,
+            seeds,
+            central_state.borsh_len(),
+        )?;
+    } else {
+        verbose_msg!("+ Updating central state");
+        CentralState::from_account_info(accounts.central_state)?;
+        Cpi::resize_account(
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.central_state,
+            central_state.borsh_len(),
+        )?;
+    }
+
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Ok(())
+}
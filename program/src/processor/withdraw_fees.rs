@@ -0,0 +1,139 @@
+//! Withdraw the protocol fees collected in a fee vault
+
+use crate::{
+    config::Config,
+    state::FEE_VAULT_PREFIX,
+    utils::{check_admin, next_optional_account_info},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_account_owner},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_token::state::Account,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The fee vault (the central state for SOL fees)
+    #[cons(writable)]
+    pub fee_vault: &'a T,
+
+    /// The destination of the fees
+    #[cons(writable)]
+    pub destination: &'a T,
+
+    /// The program data account of the tokenizer
+    pub program_data: &'a T,
+
+    /// The upgrade authority of the tokenizer
+    #[cons(signer)]
+    pub admin: &'a T,
+
+    /// The SPL token program account
+    pub spl_token_program: &'a T,
+
+    /// The authority of the fee vault (only for token fees)
+    pub fee_vault_authority: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            fee_vault: next_account_info(accounts_iter)?,
+            destination: next_account_info(accounts_iter)?,
+            program_data: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            fee_vault_authority: next_optional_account_info(accounts_iter, program_id),
+        };
+
+        // Check keys
+        let config = Config::from_program_id(program_id)?;
+        check_account_key(accounts.central_state, &config.central_state)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.central_state, program_id)?;
+
+        // Check signer
+        check_admin(program_id, accounts.program_data, accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let config = Config::from_program_id(program_id)?;
+
+    if accounts.fee_vault.key == accounts.central_state.key {
+        // SOL fees are held by the central state above its rent exempt minimum
+        let rent_exempt = Rent::get()?.minimum_balance(accounts.central_state.data_len());
+        let amount = accounts
+            .central_state
+            .lamports()
+            .saturating_sub(rent_exempt);
+        verbose_msg!("+ Withdrawing {} lamports", amount);
+        **accounts.central_state.try_borrow_mut_lamports()? -= amount;
+        **accounts.destination.try_borrow_mut_lamports()? += amount;
+        return Ok(());
+    }
+
+    check_account_owner(accounts.fee_vault, &spl_token::ID)?;
+    let vault = Account::unpack(&accounts.fee_vault.data.borrow())?;
+    // Vaults of earlier fee mints remain withdrawable
+    check_account_key(accounts.fee_vault, &config.find_fee_vault_key(&vault.mint))?;
+    let fee_vault_authority = accounts
+        .fee_vault_authority
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let (authority_key, authority_nonce) = config.find_fee_vault_authority(&vault.mint);
+    check_account_key(fee_vault_authority, &authority_key)?;
+
+    verbose_msg!("+ Withdrawing {} tokens", vault.amount);
+    let ix = spl_token::instruction::transfer(
+        &spl_token::ID,
+        accounts.fee_vault.key,
+        accounts.destination.key,
+        &authority_key,
+        &[],
+        vault.amount,
+    )?;
+    let seeds: &[&[u8]] = &[FEE_VAULT_PREFIX, &vault.mint.to_bytes(), &[authority_nonce]];
+    invoke_signed(
+        &ix,
+        &[
+            accounts.spl_token_program.clone(),
+            accounts.fee_vault.clone(),
+            accounts.destination.clone(),
+            fee_vault_authority.clone(),
+        ],
+        &[seeds],
+    )?;
+
+    Ok(())
+}
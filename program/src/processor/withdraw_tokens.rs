@@ -22,7 +22,7 @@ use {
 
 use solana_program::program_pack::Pack;
 
-use crate::state::{NftRecord, Tag};
+use crate::state::{NftRecord, NftRecordBase, NftRecordMut, Tag};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {}
@@ -95,30 +95,44 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
 
-    let mut nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveRecord)
-        .or_else(|_| NftRecord::from_account_info(accounts.nft_record, Tag::InactiveRecord))?;
+    let (
+        NftRecordBase {
+            nonce,
+            name_account,
+            owner,
+            nft_mint,
+            ..
+        },
+        is_active,
+    ) = {
+        let nft_record = NftRecordMut::from_account_info(accounts.nft_record, Tag::ActiveRecord)
+            .or_else(|_| {
+                NftRecordMut::from_account_info(accounts.nft_record, Tag::InactiveRecord)
+            })?;
+        (*nft_record.base, nft_record.is_active())
+    };
 
     let nft = Account::unpack(&accounts.nft.data.borrow())?;
 
-    if nft.mint != nft_record.nft_mint {
+    if nft.mint != nft_mint {
         msg!("+ NFT mint mismatch");
         return Err(ProgramError::InvalidArgument);
     }
 
-    if nft_record.is_active() {
+    if is_active {
         check_account_key(accounts.nft_owner, &nft.owner)?;
         if nft.amount != 1 {
             msg!("+ Invalid NFT amount, received {}", nft.amount);
             return Err(ProgramError::InvalidArgument);
         }
     } else {
-        check_account_key(accounts.nft_owner, &nft_record.owner)?
+        check_account_key(accounts.nft_owner, &owner)?
     }
 
     // Withdraw SPL token
     let token_account = Account::unpack(&accounts.token_source.data.borrow())?;
 
-    msg!("+ Withdrawing tokens {}", token_account.amount);
+    verbose_msg!("+ Withdrawing tokens {}", token_account.amount);
 
     let ix = spl_token::instruction::transfer(
         &spl_token::ID,
@@ -128,11 +142,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         &[],
         token_account.amount,
     )?;
-    let seeds: &[&[u8]] = &[
-        NftRecord::SEED,
-        &nft_record.name_account.to_bytes(),
-        &[nft_record.nonce],
-    ];
+    let seeds: &[&[u8]] = &[NftRecord::SEED, &name_account.to_bytes(), &[nonce]];
     invoke_signed(
         &ix,
         &[
@@ -152,7 +162,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         .checked_sub(minimum_rent)
         .unwrap();
 
-    msg!("+ Withdrawing native SOL {}", lamports_to_withdraw);
+    verbose_msg!("+ Withdrawing native SOL {}", lamports_to_withdraw);
     let mut nft_record_lamports = accounts.nft_record.lamports.borrow_mut();
     let mut nft_owner_lamports = accounts.nft_owner.lamports.borrow_mut();
 
@@ -160,8 +170,13 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     **nft_owner_lamports += lamports_to_withdraw;
 
     // Update NFT record owner
-    nft_record.owner = *accounts.nft_owner.key;
-    nft_record.save(&mut accounts.nft_record.data.borrow_mut());
+    let tag = if is_active {
+        Tag::ActiveRecord
+    } else {
+        Tag::InactiveRecord
+    };
+    let mut nft_record = NftRecordMut::from_account_info(accounts.nft_record, tag)?;
+    nft_record.base.owner = *accounts.nft_owner.key;
 
     Ok(())
 }
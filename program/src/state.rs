@@ -6,10 +6,16 @@ use {
 };
 
 mod central_state;
+mod lock_record;
 mod nft_record;
+mod tld_record;
 
-pub use central_state::CentralState;
-pub use nft_record::NftRecord;
+pub use central_state::{
+    CentralState, CentralStateBase, CentralStateRef, FeeVaultNonces, Fees, UriDerivation,
+};
+pub use lock_record::LockRecord;
+pub use nft_record::{NftRecord, NftRecordBase, NftRecordExtension, NftRecordMut, NftRecordNonces};
+pub use tld_record::TldRecord;
 
 pub const ROOT_DOMAIN_ACCOUNT: Pubkey = pubkey!("58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx");
 
@@ -29,6 +35,8 @@ pub const CREATOR_FEE: Creator = Creator {
 
 pub const COLLECTION_PREFIX: &[u8; 10] = b"collection";
 
+pub const FEE_VAULT_PREFIX: &[u8; 9] = b"fee_vault";
+
 pub const COLLECTION_NAME: &str = "Solana name service collection";
 
 pub const COLLECTION_URI: &str =
@@ -43,4 +51,6 @@ pub enum Tag {
     CentralState,
     ActiveRecord,
     InactiveRecord,
+    TldRecord,
+    LockRecord,
 }
@@ -1,6 +1,14 @@
+use std::cell::Ref;
+
 use bonfida_utils::BorshSize;
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use bytemuck::{Pod, Zeroable};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::OfferError;
 
 use super::Tag;
 
@@ -8,15 +16,176 @@ use super::Tag;
 #[allow(missing_docs)]
 pub struct CentralState {
     pub tag: Tag,
+
+    /// Account key appended to `base_uri` when deriving a metadata URI
+    pub uri_derivation: UriDerivation,
+
+    /// Maximum share of the royalties (in percent) the tokenizer of a domain can receive
+    pub max_creator_share: u8,
+
+    /// Protocol fees
+    pub fees: Fees,
+
+    /// Nonces of the token vault of the fee mint
+    pub fee_vault_nonces: FeeVaultNonces,
+
+    /// Base of the metadata URIs derived on-chain
+    pub base_uri: String,
+
+    /// Programs the NFT holder can invoke with the `NftRecord` as signer
+    pub allowed_programs: Vec<Pubkey>,
+}
+
+/// Protocol fees collected into the fee vault of the central state
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Clone, Copy, Default)]
+pub struct Fees {
+    /// Mint of the fees (default key for SOL)
+    pub mint: Pubkey,
+
+    /// Fee charged when a domain is tokenized
+    pub tokenization_fee: u64,
+
+    /// Fee charged when a domain is redeemed
+    pub redemption_fee: u64,
+
+    /// Share of the fees (in percent) paid to the referrer
+    pub referrer_share: u8,
+}
+
+/// Nonces of the token vault of the fee mint, set by the program whenever the fees are updated
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Clone, Copy, Default)]
+pub struct FeeVaultNonces {
+    /// Nonce of the authority of the fee vault
+    pub authority: u8,
+
+    /// Nonce of the fee vault, the associated token account of the authority
+    pub vault: u8,
+}
+
+impl Fees {
+    pub fn is_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+}
+
+/// Account key used to derive a metadata URI on-chain
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Clone, Copy)]
+pub enum UriDerivation {
+    /// `base_uri` followed by the domain name account key
+    NameAccount,
+    /// `base_uri` followed by the NFT mint key
+    Mint,
 }
 
 impl CentralState {
+    pub fn new(
+        base_uri: String,
+        uri_derivation: UriDerivation,
+        max_creator_share: u8,
+        fees: Fees,
+        fee_vault_nonces: FeeVaultNonces,
+        allowed_programs: Vec<Pubkey>,
+    ) -> Self {
+        Self {
+            tag: Tag::CentralState,
+            uri_derivation,
+            max_creator_share,
+            fees,
+            fee_vault_nonces,
+            base_uri,
+            allowed_programs,
+        }
+    }
+
     pub fn find_key(program_id: &Pubkey) -> (Pubkey, u8) {
         let seeds: &[&[u8]] = &[&program_id.to_bytes()];
         Pubkey::find_program_address(seeds, program_id)
     }
 
-    pub fn save(&self, mut dst: &mut [u8]) {
-        self.serialize(&mut dst).unwrap()
+    /// The metadata URI of a domain when it is not provided by the metadata signer
+    pub fn derive_uri(&self, name_account: &Pubkey, nft_mint: &Pubkey) -> String {
+        match self.uri_derivation {
+            UriDerivation::NameAccount => format!("{}{}", self.base_uri, name_account),
+            UriDerivation::Mint => format!("{}{}", self.base_uri, nft_mint),
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)?;
+        Ok(())
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<CentralState, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        check_tag(data)?;
+        let result = CentralState::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+fn check_tag(data: &[u8]) -> ProgramResult {
+    match data.first() {
+        None => Err(OfferError::Uninitialized.into()),
+        Some(tag) if *tag != Tag::CentralState as u8 => Err(OfferError::DataTypeMismatch.into()),
+        Some(_) => Ok(()),
+    }
+}
+
+/// The fixed size fields of the `CentralState`, which precede its URI base and allowed programs,
+/// in their on-chain layout
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct CentralStateBase {
+    pub tag: u8,
+    pub uri_derivation: u8,
+    pub max_creator_share: u8,
+    pub fee_mint: Pubkey,
+    pub tokenization_fee: [u8; 8],
+    pub redemption_fee: [u8; 8],
+    pub referrer_share: u8,
+    pub fee_vault_authority_nonce: u8,
+    pub fee_vault_nonce: u8,
+}
+
+impl CentralStateBase {
+    pub const LEN: usize = 1 + 1 + 1 + 32 + 8 + 8 + 1 + 2;
+}
+
+const _: () = assert!(std::mem::size_of::<CentralStateBase>() == CentralStateBase::LEN);
+
+/// A `CentralState` read in place, without deserializing its URI base and allowed programs
+pub struct CentralStateRef<'a> {
+    pub base: Ref<'a, CentralStateBase>,
+}
+
+#[allow(missing_docs)]
+impl<'a> CentralStateRef<'a> {
+    pub fn from_account_info(a: &'a AccountInfo) -> Result<Self, ProgramError> {
+        let data = Ref::map(a.try_borrow_data()?, |data| &**data);
+        check_tag(&data)?;
+        if data.len() < CentralStateBase::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self {
+            base: Ref::map(data, |data| {
+                bytemuck::from_bytes(&data[..CentralStateBase::LEN])
+            }),
+        })
+    }
+
+    pub fn fees(&self) -> Fees {
+        Fees {
+            mint: self.base.fee_mint,
+            tokenization_fee: u64::from_le_bytes(self.base.tokenization_fee),
+            redemption_fee: u64::from_le_bytes(self.base.redemption_fee),
+            referrer_share: self.base.referrer_share,
+        }
+    }
+
+    pub fn fee_vault_nonces(&self) -> FeeVaultNonces {
+        FeeVaultNonces {
+            authority: self.base.fee_vault_authority_nonce,
+            vault: self.base.fee_vault_nonce,
+        }
     }
 }
@@ -0,0 +1,84 @@
+use bonfida_utils::{checks::check_account_key, BorshSize};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::OfferError;
+
+use super::Tag;
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+#[allow(missing_docs)]
+pub struct LockRecord {
+    /// Tag
+    pub tag: Tag,
+
+    /// Nonce
+    pub nonce: u8,
+
+    /// Token account of the holder that can be locked
+    pub nft_account: Pubkey,
+
+    /// Mint of the locked NFT
+    pub nft_mint: Pubkey,
+
+    /// Authority allowed to freeze and thaw `nft_account`
+    pub lock_authority: Pubkey,
+}
+
+#[allow(missing_docs)]
+impl LockRecord {
+    pub const SEED: &'static [u8; 11] = b"lock_record";
+
+    pub fn new(nonce: u8, nft_account: Pubkey, nft_mint: Pubkey, lock_authority: Pubkey) -> Self {
+        Self {
+            tag: Tag::LockRecord,
+            nonce,
+            nft_account,
+            nft_mint,
+            lock_authority,
+        }
+    }
+
+    pub fn find_key(nft_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[LockRecord::SEED, &nft_account.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    /// Verifies the key of the record of `nft_account` with its stored nonce
+    pub fn check_key(
+        &self,
+        account: &AccountInfo,
+        nft_account: &Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if &self.nft_account != nft_account {
+            msg!("+ The lock record does not belong to {}", nft_account);
+            return Err(ProgramError::InvalidArgument);
+        }
+        let key = Pubkey::create_program_address(
+            &[LockRecord::SEED, &nft_account.to_bytes(), &[self.nonce]],
+            program_id,
+        )?;
+        check_account_key(account, &key)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)?;
+        Ok(())
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<LockRecord, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.is_empty() {
+            return Err(OfferError::Uninitialized.into());
+        }
+        if data[0] != Tag::LockRecord as u8 {
+            return Err(OfferError::DataTypeMismatch.into());
+        }
+        let result = LockRecord::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
@@ -1,6 +1,13 @@
+use std::cell::RefMut;
+
+use bonfida_utils::checks::check_account_key;
 use bonfida_utils::BorshSize;
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use bytemuck::{Pod, Zeroable};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
 
 use crate::error::OfferError;
 
@@ -23,19 +30,61 @@ pub struct NftRecord {
 
     /// NFT mint
     pub nft_mint: Pubkey,
+
+    /// Class of the name account (default key if the domain has no class)
+    pub class: Pubkey,
+
+    /// Whether the NFT is frozen in the account of its holder
+    pub soulbound: bool,
+
+    /// Nonces of the accounts of the NFT (zero for legacy records, which did not store them)
+    pub nonces: NftRecordNonces,
+}
+
+/// Nonces of the PDAs of a tokenized domain, stored so they are not searched again
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Clone, Copy, Default, Pod, Zeroable)]
+#[repr(C)]
+pub struct NftRecordNonces {
+    /// Nonce of the NFT mint
+    pub mint: u8,
+
+    /// Nonce of the Metaplex metadata of the NFT
+    pub metadata: u8,
+
+    /// Nonce of the Metaplex master edition of the NFT
+    pub edition: u8,
+
+    /// Nonce of the central state token account holding the NFT while the domain is redeemed
+    pub escrow: u8,
 }
 
 #[allow(missing_docs)]
 impl NftRecord {
     pub const SEED: &'static [u8; 10] = b"nft_record";
 
-    pub fn new(nonce: u8, owner: Pubkey, name_account: Pubkey, nft_mint: Pubkey) -> Self {
+    pub const LEN: usize = 1 + 1 + 4 * 32 + 1 + 4;
+
+    /// Length of the records created before the name class was recorded
+    pub const LEGACY_LEN: usize = 1 + 1 + 3 * 32;
+
+    pub fn new(
+        nonce: u8,
+        owner: Pubkey,
+        name_account: Pubkey,
+        nft_mint: Pubkey,
+        class: Pubkey,
+        soulbound: bool,
+        nonces: NftRecordNonces,
+    ) -> Self {
         Self {
             tag: Tag::ActiveRecord,
             nonce,
             owner,
             name_account,
             nft_mint,
+            class,
+            soulbound,
+            nonces,
         }
     }
 
@@ -44,20 +93,161 @@ impl NftRecord {
         Pubkey::find_program_address(seeds, program_id)
     }
 
-    pub fn save(&self, mut dst: &mut [u8]) {
-        self.serialize(&mut dst).unwrap()
+    /// Verifies the key of the record of `name_account` with its stored nonce
+    pub fn check_key(
+        &self,
+        account: &AccountInfo,
+        name_account: &Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        check_record_key(
+            account,
+            &self.name_account,
+            self.nonce,
+            name_account,
+            program_id,
+        )
+    }
+
+    /// Legacy records are only written to while the fields they omit keep their default values
+    pub fn save(&self, dst: &mut [u8]) -> ProgramResult {
+        let data = self.try_to_vec()?;
+        let len = data.len().min(dst.len());
+        dst[..len].copy_from_slice(&data[..len]);
+        Ok(())
     }
 
     pub fn from_account_info(a: &AccountInfo, tag: Tag) -> Result<NftRecord, ProgramError> {
-        let mut data = &a.data.borrow() as &[u8];
+        let mut data = a.data.borrow().to_vec();
         if data[0] != tag as u8 {
             return Err(OfferError::DataTypeMismatch.into());
         }
-        let result = NftRecord::deserialize(&mut data)?;
+        if data.len() == Self::LEGACY_LEN {
+            data.resize(Self::LEN, 0);
+        }
+        let result = NftRecord::deserialize(&mut data.as_slice())?;
         Ok(result)
     }
 
+    pub fn has_class(&self) -> bool {
+        self.class != Pubkey::default()
+    }
+
     pub fn is_active(&self) -> bool {
         self.tag == Tag::ActiveRecord
     }
 }
+
+/// Fields common to every `NftRecord`, in their on-chain layout
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct NftRecordBase {
+    pub tag: u8,
+    pub nonce: u8,
+    pub name_account: Pubkey,
+    pub owner: Pubkey,
+    pub nft_mint: Pubkey,
+}
+
+/// Fields missing from legacy `NftRecord`s, in their on-chain layout
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct NftRecordExtension {
+    pub class: Pubkey,
+    pub soulbound: u8,
+    pub nonces: NftRecordNonces,
+}
+
+const _: () = assert!(std::mem::size_of::<NftRecordBase>() == NftRecord::LEGACY_LEN);
+const _: () = assert!(
+    std::mem::size_of::<NftRecordBase>() + std::mem::size_of::<NftRecordExtension>()
+        == NftRecord::LEN
+);
+
+/// An `NftRecord` read and written in place, without Borsh
+///
+/// The view borrows the account data, it must be dropped before the record is passed to a CPI.
+pub struct NftRecordMut<'a> {
+    pub base: RefMut<'a, NftRecordBase>,
+
+    /// `None` for legacy records
+    pub extension: Option<RefMut<'a, NftRecordExtension>>,
+}
+
+#[allow(missing_docs)]
+impl<'a> NftRecordMut<'a> {
+    pub fn from_account_info(a: &'a AccountInfo, tag: Tag) -> Result<Self, ProgramError> {
+        let data = RefMut::map(a.try_borrow_mut_data()?, |data| &mut **data);
+        if data.first() != Some(&(tag as u8)) {
+            return Err(OfferError::DataTypeMismatch.into());
+        }
+        let (base, extension) = match data.len() {
+            NftRecord::LEN => {
+                let (base, extension) =
+                    RefMut::map_split(data, |data| data.split_at_mut(NftRecord::LEGACY_LEN));
+                (base, Some(extension))
+            }
+            NftRecord::LEGACY_LEN => (data, None),
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(Self {
+            base: RefMut::map(base, bytemuck::from_bytes_mut),
+            extension: extension.map(|extension| RefMut::map(extension, bytemuck::from_bytes_mut)),
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.base.tag == Tag::ActiveRecord as u8
+    }
+
+    pub fn class(&self) -> Pubkey {
+        self.extension
+            .as_ref()
+            .map_or_else(Pubkey::default, |extension| extension.class)
+    }
+
+    pub fn soulbound(&self) -> bool {
+        self.extension
+            .as_ref()
+            .map_or(false, |extension| extension.soulbound != 0)
+    }
+
+    /// The stored nonces of the accounts of the NFT, `None` for legacy records
+    pub fn nonces(&self) -> Option<NftRecordNonces> {
+        self.extension.as_ref().map(|extension| extension.nonces)
+    }
+
+    /// Verifies the key of the record of `name_account` with its stored nonce
+    pub fn check_key(
+        &self,
+        account: &AccountInfo,
+        name_account: &Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        check_record_key(
+            account,
+            &self.base.name_account,
+            self.base.nonce,
+            name_account,
+            program_id,
+        )
+    }
+}
+
+fn check_record_key(
+    account: &AccountInfo,
+    record_name_account: &Pubkey,
+    nonce: u8,
+    name_account: &Pubkey,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if record_name_account != name_account {
+        msg!("+ The NFT record does not belong to {}", name_account);
+        return Err(ProgramError::InvalidArgument);
+    }
+    let key = Pubkey::create_program_address(
+        &[NftRecord::SEED, &name_account.to_bytes(), &[nonce]],
+        program_id,
+    )?;
+    check_account_key(account, &key)
+}
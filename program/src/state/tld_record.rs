@@ -0,0 +1,131 @@
+use bonfida_utils::checks::check_account_key;
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::OfferError;
+
+use super::{Tag, COLLECTION_PREFIX, ROOT_DOMAIN_ACCOUNT};
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+#[allow(missing_docs)]
+pub struct TldRecord {
+    /// Tag
+    pub tag: Tag,
+
+    /// Nonce
+    pub nonce: u8,
+
+    /// Nonce of the collection mint
+    pub collection_nonce: u8,
+
+    /// Nonce of the Metaplex metadata of the collection
+    pub collection_metadata_nonce: u8,
+
+    /// Nonce of the Metaplex master edition of the collection
+    pub collection_edition_nonce: u8,
+
+    /// Parent name account of the TLD
+    pub tld: Pubkey,
+
+    /// Name of the collection
+    pub collection_name: String,
+
+    /// URI of the collection metadata
+    pub collection_uri: String,
+
+    /// Symbol of the NFTs
+    pub symbol: String,
+}
+
+#[allow(missing_docs)]
+impl TldRecord {
+    pub const SEED: &'static [u8; 10] = b"tld_record";
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        nonce: u8,
+        collection_nonce: u8,
+        collection_metadata_nonce: u8,
+        collection_edition_nonce: u8,
+        tld: Pubkey,
+        collection_name: String,
+        collection_uri: String,
+        symbol: String,
+    ) -> Self {
+        Self {
+            tag: Tag::TldRecord,
+            nonce,
+            collection_nonce,
+            collection_metadata_nonce,
+            collection_edition_nonce,
+            tld,
+            collection_name,
+            collection_uri,
+            symbol,
+        }
+    }
+
+    pub fn find_key(tld: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[TldRecord::SEED, &tld.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    /// The collection mint of the TLD (`.sol` keeps its original derivation)
+    pub fn find_collection_key(tld: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        if tld == &ROOT_DOMAIN_ACCOUNT {
+            return Pubkey::find_program_address(
+                &[COLLECTION_PREFIX, &program_id.to_bytes()],
+                program_id,
+            );
+        }
+        Pubkey::find_program_address(
+            &[COLLECTION_PREFIX, &program_id.to_bytes(), &tld.to_bytes()],
+            program_id,
+        )
+    }
+
+    /// Verifies the key of the record with its stored nonce
+    pub fn check_key(&self, account: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+        let key = Pubkey::create_program_address(
+            &[TldRecord::SEED, &self.tld.to_bytes(), &[self.nonce]],
+            program_id,
+        )?;
+        check_account_key(account, &key)
+    }
+
+    /// The collection mint of the TLD, derived from the stored nonce
+    pub fn collection_key(&self, program_id: &Pubkey) -> Result<Pubkey, ProgramError> {
+        let key = Pubkey::create_program_address(
+            &[
+                COLLECTION_PREFIX,
+                &program_id.to_bytes(),
+                &self.tld.to_bytes(),
+                &[self.collection_nonce],
+            ],
+            program_id,
+        )?;
+        Ok(key)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)?;
+        Ok(())
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<TldRecord, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        match data.first() {
+            None => return Err(OfferError::Uninitialized.into()),
+            Some(tag) if *tag != Tag::TldRecord as u8 => {
+                return Err(OfferError::DataTypeMismatch.into())
+            }
+            Some(_) => (),
+        }
+        let result = TldRecord::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
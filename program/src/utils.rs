@@ -1,15 +1,54 @@
 use {
-    bonfida_utils::checks::check_account_owner,
+    bonfida_utils::checks::{check_account_key, check_account_owner, check_signer},
+    mpl_token_metadata::{
+        accounts::{MasterEdition, Metadata},
+        types::Creator,
+    },
     solana_program::{
-        account_info::AccountInfo, entrypoint::ProgramResult, hash::hashv, msg,
+        account_info::{next_account_info, AccountInfo},
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        entrypoint::ProgramResult,
+        hash::hashv,
+        msg,
+        program::{invoke, invoke_signed},
         program_error::ProgramError,
+        program_option::COption,
+        program_pack::Pack,
+        program_utils::limited_deserialize,
+        pubkey::Pubkey,
+        system_instruction,
+    },
+    spl_name_service::state::{get_seeds_and_key, NameRecordHeader, HASH_PREFIX},
+    spl_token::state::Mint,
+};
+
+use crate::{
+    config::Config,
+    state::{
+        CentralStateRef, FeeVaultNonces, Fees, NftRecord, NftRecordNonces, TldRecord,
+        COLLECTION_NAME, COLLECTION_URI, CREATOR_FEE, META_SYMBOL,
     },
-    spl_name_service::state::{get_seeds_and_key, HASH_PREFIX},
 };
 
-use crate::state::ROOT_DOMAIN_ACCOUNT;
+/// Returns the next optional account, absent optional accounts are passed as the program ID so
+/// that the optional accounts following them keep their position
+pub fn next_optional_account_info<'a, 'b>(
+    iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+) -> Option<&'a AccountInfo<'b>> {
+    next_account_info(iter)
+        .ok()
+        .filter(|account| account.key != program_id)
+}
 
-pub fn check_name(name: &str, account: &AccountInfo) -> ProgramResult {
+/// Verifies that a name account derives from `name` under `tld`, the name service derives the
+/// keys of class domains with their class
+pub fn check_name(
+    name: &str,
+    account: &AccountInfo,
+    class: &Pubkey,
+    tld: &Pubkey,
+) -> ProgramResult {
     check_account_owner(account, &spl_name_service::ID)?;
 
     let hashed_name = hashv(&[(HASH_PREFIX.to_owned() + name).as_bytes()])
@@ -21,18 +60,365 @@ pub fn check_name(name: &str, account: &AccountInfo) -> ProgramResult {
         return Err(ProgramError::InvalidArgument);
     }
 
-    let (name_account_key, _) = get_seeds_and_key(
-        &spl_name_service::ID,
-        hashed_name,
-        None,
-        Some(&ROOT_DOMAIN_ACCOUNT),
-    );
+    let class = (class != &Pubkey::default()).then_some(class);
+    let (name_account_key, _) =
+        get_seeds_and_key(&spl_name_service::ID, hashed_name, class, Some(tld));
 
     if &name_account_key != account.key {
         msg!("Provided wrong name account");
-        #[cfg(not(feature = "devnet"))]
         return Err(ProgramError::InvalidArgument);
     }
 
     Ok(())
 }
+
+/// Verifies that the class of a name account signs when the name has a class, and returns the
+/// class to pass to the name service
+pub fn check_name_class(
+    class: &Pubkey,
+    name_class: Option<&AccountInfo>,
+) -> Result<Option<Pubkey>, ProgramError> {
+    if class == &Pubkey::default() {
+        if let Some(name_class) = name_class {
+            msg!("+ The domain has no name class, got {}", name_class.key);
+            return Err(ProgramError::InvalidArgument);
+        }
+        return Ok(None);
+    }
+    match name_class {
+        Some(name_class) if name_class.key == class && name_class.is_signer => Ok(Some(*class)),
+        _ => {
+            msg!("+ The name class {} must sign", class);
+            Err(ProgramError::MissingRequiredSignature)
+        }
+    }
+}
+
+/// Verifies that `len` bytes at `offset` are within the data of a name account
+pub fn check_name_data_range(name_account: &AccountInfo, offset: u32, len: usize) -> ProgramResult {
+    let data_len = name_account
+        .data_len()
+        .saturating_sub(NameRecordHeader::LEN);
+    match (offset as usize).checked_add(len) {
+        Some(end) if end <= data_len => Ok(()),
+        _ => {
+            msg!("+ Write out of the bounds of the name account");
+            Err(ProgramError::InvalidArgument)
+        }
+    }
+}
+
+/// Writes `data` at `offset` in a name account owned by its NFT record, the name service
+/// requires the class to sign the update of domains that have one
+pub fn write_name_data<'a>(
+    spl_name_service_program: &AccountInfo<'a>,
+    name_account: &AccountInfo<'a>,
+    nft_record: &AccountInfo<'a>,
+    nft_record_nonce: u8,
+    name_class: Option<&AccountInfo<'a>>,
+    offset: u32,
+    data: Vec<u8>,
+) -> ProgramResult {
+    check_name_data_range(name_account, offset, data.len())?;
+    let signer = name_class.unwrap_or(nft_record);
+    let ix = spl_name_service::instruction::update(
+        spl_name_service::ID,
+        offset,
+        data,
+        *name_account.key,
+        *signer.key,
+        None,
+    )?;
+    let seeds: &[&[u8]] = &[
+        NftRecord::SEED,
+        &name_account.key.to_bytes(),
+        &[nft_record_nonce],
+    ];
+    invoke_signed(
+        &ix,
+        &[
+            spl_name_service_program.clone(),
+            name_account.clone(),
+            signer.clone(),
+        ],
+        &[seeds],
+    )
+}
+
+/// Zeroes the data of a name account owned by its NFT record
+pub fn wipe_name_data<'a>(
+    spl_name_service_program: &AccountInfo<'a>,
+    name_account: &AccountInfo<'a>,
+    nft_record: &AccountInfo<'a>,
+    nft_record_nonce: u8,
+    name_class: Option<&AccountInfo<'a>>,
+) -> ProgramResult {
+    verbose_msg!("+ Wiping domain data");
+    let len = name_account
+        .data_len()
+        .saturating_sub(NameRecordHeader::LEN);
+    write_name_data(
+        spl_name_service_program,
+        name_account,
+        nft_record,
+        nft_record_nonce,
+        name_class,
+        0,
+        vec![0; len],
+    )
+}
+
+/// Verifies that `share` does not exceed the maximum creator share of the central state
+pub fn check_creator_share(
+    program_id: &Pubkey,
+    central_state: &AccountInfo,
+    share: u8,
+) -> ProgramResult {
+    if share == 0 {
+        return Ok(());
+    }
+    check_account_owner(central_state, program_id)?;
+    let max_creator_share = CentralStateRef::from_account_info(central_state)?
+        .base
+        .max_creator_share;
+    if share > max_creator_share {
+        msg!(
+            "+ Creator share exceeds the maximum of {}",
+            max_creator_share
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// The creators of a domain NFT, `creator` receives `share` percent of the royalties
+pub fn nft_creators(central_state: &Pubkey, creator: &Pubkey, share: u8) -> Vec<Creator> {
+    let central_creator = Creator {
+        address: *central_state,
+        verified: true,
+        share: 0,
+    };
+    if share == 0 {
+        return vec![central_creator, CREATOR_FEE];
+    }
+    vec![
+        central_creator,
+        Creator {
+            share: CREATOR_FEE.share - share,
+            ..CREATOR_FEE
+        },
+        Creator {
+            address: *creator,
+            verified: false,
+            share,
+        },
+    ]
+}
+
+/// The protocol fees configured in the central state and the nonces of their vault (none until
+/// the central state is created)
+pub fn get_fees(
+    program_id: &Pubkey,
+    central_state: &AccountInfo,
+) -> Result<(Fees, FeeVaultNonces), ProgramError> {
+    if central_state.data_is_empty() {
+        return Ok(Default::default());
+    }
+    check_account_owner(central_state, program_id)?;
+    let central_state = CentralStateRef::from_account_info(central_state)?;
+    Ok((central_state.fees(), central_state.fee_vault_nonces()))
+}
+
+/// Transfers a protocol fee of `amount` from `source` to the fee vault, `referrer` receives its
+/// share of the fee unless it is the fee vault itself
+#[allow(clippy::too_many_arguments)]
+pub fn collect_fee<'a>(
+    config: &Config,
+    fees: &Fees,
+    fee_vault_nonces: &FeeVaultNonces,
+    amount: u64,
+    authority: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    fee_vault: &AccountInfo<'a>,
+    referrer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    spl_token_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    check_account_key(
+        fee_vault,
+        &config.fee_vault_key(&fees.mint, fee_vault_nonces)?,
+    )?;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let referrer_amount = if referrer.key == fee_vault.key {
+        0
+    } else {
+        (amount as u128 * fees.referrer_share as u128 / 100) as u64
+    };
+    verbose_msg!("+ Collecting fee of {}", amount);
+    for (destination, amount) in [
+        (fee_vault, amount - referrer_amount),
+        (referrer, referrer_amount),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        if fees.is_sol() {
+            check_account_key(source, authority.key)?;
+            invoke(
+                &system_instruction::transfer(authority.key, destination.key, amount),
+                &[
+                    system_program.clone(),
+                    authority.clone(),
+                    destination.clone(),
+                ],
+            )?;
+        } else {
+            let ix = spl_token::instruction::transfer(
+                &spl_token::ID,
+                source.key,
+                destination.key,
+                authority.key,
+                &[],
+                amount,
+            )?;
+            invoke(
+                &ix,
+                &[
+                    spl_token_program.clone(),
+                    source.clone(),
+                    destination.clone(),
+                    authority.clone(),
+                ],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies that `authority` is the upgrade authority of the program
+pub fn check_admin(
+    program_id: &Pubkey,
+    program_data: &AccountInfo,
+    authority: &AccountInfo,
+) -> ProgramResult {
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[&program_id.to_bytes()], &bpf_loader_upgradeable::ID);
+    check_account_key(program_data, &program_data_key)?;
+    check_account_owner(program_data, &bpf_loader_upgradeable::ID)?;
+    check_signer(authority)?;
+
+    let state: UpgradeableLoaderState = limited_deserialize(
+        &program_data.data.borrow(),
+        UpgradeableLoaderState::size_of_programdata_metadata() as u64,
+    )
+    .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    match state {
+        UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address: Some(upgrade_authority),
+            ..
+        } if &upgrade_authority == authority.key => Ok(()),
+        _ => {
+            msg!("+ Signer is not the program upgrade authority");
+            Err(ProgramError::InvalidArgument)
+        }
+    }
+}
+
+/// The collection of the NFTs of a TLD
+pub struct Collection {
+    pub tld: Pubkey,
+    pub mint: Pubkey,
+    pub nonce: u8,
+    pub metadata_nonce: u8,
+    pub edition_nonce: u8,
+    pub name: String,
+    pub uri: String,
+    pub symbol: String,
+}
+
+/// Returns the collection described by `tld_record`, or the `.sol` collection when omitted
+pub fn get_collection(
+    program_id: &Pubkey,
+    tld_record: Option<&AccountInfo>,
+) -> Result<Collection, ProgramError> {
+    let config = Config::from_program_id(program_id)?;
+    match tld_record {
+        None => Ok(Collection {
+            tld: config.root_domain,
+            mint: config.collection_mint,
+            nonce: config.collection_nonce,
+            metadata_nonce: config.collection_metadata_nonce,
+            edition_nonce: config.collection_edition_nonce,
+            name: COLLECTION_NAME.to_string(),
+            uri: COLLECTION_URI.to_string(),
+            symbol: META_SYMBOL.to_string(),
+        }),
+        Some(tld_record) => {
+            check_account_owner(tld_record, program_id)?;
+            let record = TldRecord::from_account_info(tld_record)?;
+            record.check_key(tld_record, program_id)?;
+
+            Ok(Collection {
+                tld: record.tld,
+                mint: record.collection_key(program_id)?,
+                nonce: record.collection_nonce,
+                metadata_nonce: record.collection_metadata_nonce,
+                edition_nonce: record.collection_edition_nonce,
+                name: record.collection_name,
+                uri: record.collection_uri,
+                symbol: record.symbol,
+            })
+        }
+    }
+}
+
+/// The Metaplex metadata of an NFT, derived from the nonce stored in its record unless the record
+/// is a legacy record
+pub fn nft_metadata_key(
+    mint: &Pubkey,
+    nonces: Option<NftRecordNonces>,
+) -> Result<Pubkey, ProgramError> {
+    match nonces {
+        Some(nonces) => Ok(Metadata::create_pda(*mint, nonces.metadata)?),
+        None => Ok(Metadata::find_pda(mint).0),
+    }
+}
+
+/// The Metaplex master edition of an NFT, derived from the nonce stored in its record unless the
+/// record is a legacy record
+pub fn nft_edition_key(
+    mint: &Pubkey,
+    nonces: Option<NftRecordNonces>,
+) -> Result<Pubkey, ProgramError> {
+    match nonces {
+        Some(nonces) => Ok(MasterEdition::create_pda(*mint, nonces.edition)?),
+        None => Ok(MasterEdition::find_pda(mint).0),
+    }
+}
+
+/// Verifies that a mint created by the program is initialized and still controlled by the central
+/// state, or by the master edition which takes over its authorities once the NFT is minted
+pub fn check_program_mint(
+    mint: &AccountInfo,
+    edition: &Pubkey,
+    central_state: &Pubkey,
+) -> ProgramResult {
+    check_account_owner(mint, &spl_token::ID)?;
+    let mint_info = Mint::unpack(&mint.data.borrow())?;
+    let is_authority = |authority: COption<Pubkey>| {
+        authority == COption::Some(*central_state) || authority == COption::Some(*edition)
+    };
+    if mint_info.decimals != 0
+        || !is_authority(mint_info.mint_authority)
+        || !is_authority(mint_info.freeze_authority)
+    {
+        msg!("+ The mint authorities do not belong to the program");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
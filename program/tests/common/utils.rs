@@ -1,12 +1,20 @@
 use std::str::FromStr;
 
-use solana_program::instruction::Instruction;
+use name_tokenizer::config::LOCALNET;
+
+use solana_program::account_info::AccountInfo;
+use solana_program::bpf_loader_upgradeable;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
 use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
 use solana_sdk::account::Account;
 use solana_sdk::signature::Signer;
-use solana_sdk::{signature::Keypair, transaction::Transaction};
+use solana_sdk::{
+    signature::Keypair, signer::keypair::keypair_from_seed, transaction::Transaction,
+};
 use spl_token::state::Mint;
 
 // Utils
@@ -24,6 +32,23 @@ pub async fn sign_send_instructions(
     ctx.banks_client.process_transaction(transaction).await
 }
 
+/// Simulates the instructions and returns the return data of the transaction
+pub async fn simulate_return_data(
+    ctx: &mut ProgramTestContext,
+    instructions: Vec<Instruction>,
+) -> Result<Option<Vec<u8>>, BanksClientError> {
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&ctx.payer.pubkey()));
+    transaction.sign(&[&ctx.payer], ctx.last_blockhash);
+    let simulation = ctx.banks_client.simulate_transaction(transaction).await?;
+    if let Some(Err(e)) = simulation.result {
+        return Err(e.into());
+    }
+    Ok(simulation
+        .simulation_details
+        .and_then(|d| d.return_data)
+        .map(|r| r.data))
+}
+
 pub fn mint_bootstrap(
     address: Option<&str>,
     decimals: u8,
@@ -54,3 +79,59 @@ pub fn mint_bootstrap(
     );
     (address, mint_info)
 }
+
+pub fn program_data_bootstrap(
+    program_test: &mut ProgramTest,
+    upgrade_authority: &Pubkey,
+) -> Pubkey {
+    let (address, _) = Pubkey::find_program_address(
+        &[&LOCALNET.program_id.to_bytes()],
+        &bpf_loader_upgradeable::ID,
+    );
+    // UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address }
+    let data = [
+        &3u32.to_le_bytes()[..],
+        &0u64.to_le_bytes(),
+        &[1],
+        &upgrade_authority.to_bytes(),
+    ]
+    .concat();
+    program_test.add_account(
+        address,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: bpf_loader_upgradeable::ID,
+            ..Account::default()
+        },
+    );
+    address
+}
+
+/// A program forwarding its instruction data to the program passed as first account, as any
+/// program relaying CPIs would
+pub fn process_forward(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (program, remaining_accounts) = accounts.split_first().unwrap();
+    let ix = Instruction {
+        program_id: *program.key,
+        accounts: remaining_accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: *a.key,
+                is_signer: a.is_signer,
+                is_writable: a.is_writable,
+            })
+            .collect(),
+        data: instruction_data.to_vec(),
+    };
+    invoke(&ix, accounts)
+}
+
+/// The metadata signer of `LOCALNET_ATTESTED`, derived from a public seed
+pub fn attested_metadata_signer() -> Keypair {
+    keypair_from_seed(b"name tokenizer test metadata signer").unwrap()
+}
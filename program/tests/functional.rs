@@ -1,16 +1,25 @@
 use {
+    bonfida_utils::BorshSize,
     borsh::{BorshDeserialize, BorshSerialize},
     name_tokenizer::{
+        config::{Config, DEVNET, LOCALNET, LOCALNET_ATTESTED, MAINNET},
         entrypoint::process_instruction,
         instruction::{
-            create_collection, create_mint, create_nft, redeem_nft, unverify_nft, withdraw_tokens,
+            create_collection, create_mint, create_mint_and_nft, create_nft, create_nft_attested,
+            ed25519_instruction, edit_creators, execute, get_effective_owner, manage_data,
+            redeem_nft, register_tld, set_lock, set_lock_authority, sync_owner, unverify_nft,
+            update_central_state, withdraw_fees, withdraw_tokens,
         },
         state::{
-            CentralState, NftRecord, COLLECTION_PREFIX, METADATA_SIGNER, MINT_PREFIX,
-            ROOT_DOMAIN_ACCOUNT,
+            CentralState, CentralStateRef, FeeVaultNonces, Fees, LockRecord, NftRecord,
+            NftRecordMut, NftRecordNonces, Tag, TldRecord, UriDerivation, COLLECTION_PREFIX,
+            METADATA_SIGNER, MINT_PREFIX, ROOT_DOMAIN_ACCOUNT,
         },
     },
-    solana_program::{hash::hashv, pubkey::Pubkey, system_instruction, system_program, sysvar},
+    solana_program::{
+        account_info::AccountInfo, ed25519_program, hash::hashv, instruction::AccountMeta,
+        program_pack::Pack, pubkey::Pubkey, system_instruction, system_program, sysvar,
+    },
     solana_program_test::{processor, ProgramTest},
     solana_sdk::{
         account::Account,
@@ -19,7 +28,7 @@ use {
     spl_associated_token_account::{
         get_associated_token_address, instruction::create_associated_token_account,
     },
-    spl_name_service::state::{get_seeds_and_key, HASH_PREFIX},
+    spl_name_service::state::{get_seeds_and_key, NameRecordHeader, HASH_PREFIX},
 };
 
 pub mod common;
@@ -27,7 +36,10 @@ pub mod common;
 use mpl_token_metadata::accounts::{MasterEdition, Metadata};
 use name_tokenizer::instruction::edit_data;
 
-use crate::common::utils::{mint_bootstrap, sign_send_instructions};
+use crate::common::utils::{
+    attested_metadata_signer, mint_bootstrap, process_forward, program_data_bootstrap,
+    sign_send_instructions, simulate_return_data,
+};
 
 #[tokio::test]
 async fn test_offer() {
@@ -38,7 +50,7 @@ async fn test_offer() {
 
     let mut program_test = ProgramTest::new(
         "name_tokenizer",
-        name_tokenizer::ID,
+        LOCALNET.program_id,
         processor!(process_instruction),
     );
     program_test.add_program("spl_name_service", spl_name_service::ID, None);
@@ -79,6 +91,57 @@ async fn test_offer() {
         },
     );
 
+    // Create a subdomain owned by Alice
+    let hashed_sub_name = hashv(&[(HASH_PREFIX.to_owned() + "\0sub").as_bytes()])
+        .as_ref()
+        .to_vec();
+    let (sub_key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        hashed_sub_name,
+        None,
+        Some(&name_key),
+    );
+    program_test.add_account(
+        sub_key,
+        Account {
+            lamports: 1_000_000,
+            data: spl_name_service::state::NameRecordHeader {
+                parent_name: name_key,
+                owner: alice.pubkey(),
+                class: Pubkey::default(),
+            }
+            .try_to_vec()
+            .unwrap(),
+            owner: spl_name_service::id(),
+            ..Account::default()
+        },
+    );
+
+    let hashed_sub2_name = hashv(&[(HASH_PREFIX.to_owned() + "\0sub2").as_bytes()])
+        .as_ref()
+        .to_vec();
+    let (sub2_key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        hashed_sub2_name,
+        None,
+        Some(&name_key),
+    );
+    program_test.add_account(
+        sub2_key,
+        Account {
+            lamports: 1_000_000,
+            data: spl_name_service::state::NameRecordHeader {
+                parent_name: name_key,
+                owner: alice.pubkey(),
+                class: Pubkey::default(),
+            }
+            .try_to_vec()
+            .unwrap(),
+            owner: spl_name_service::id(),
+            ..Account::default()
+        },
+    );
+
     program_test.add_account(
         alice.pubkey(),
         Account {
@@ -107,15 +170,26 @@ async fn test_offer() {
     /////
     // Create central state
     ////
-    let (central_key, _) = CentralState::find_key(&name_tokenizer::ID);
+    let (central_key, _) = CentralState::find_key(&LOCALNET.program_id);
 
     ////
     // Create mint
     ////
     let (nft_mint, _) =
-        Pubkey::find_program_address(&[MINT_PREFIX, &name_key.to_bytes()], &name_tokenizer::ID);
+        Pubkey::find_program_address(&[MINT_PREFIX, &name_key.to_bytes()], &LOCALNET.program_id);
+
+    // Lamports sent to the PDAs beforehand must not block their creation
+    let (nft_record, _) = NftRecord::find_key(&name_key, &LOCALNET.program_id);
+    let ixs = vec![
+        system_instruction::transfer(&prg_test_ctx.payer.pubkey(), &nft_mint, 1_000_000),
+        system_instruction::transfer(&prg_test_ctx.payer.pubkey(), &nft_record, 1_000),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![])
+        .await
+        .unwrap();
 
     let ix = create_mint(
+        LOCALNET.program_id,
         create_mint::Accounts {
             mint: &nft_mint,
             central_state: &central_key,
@@ -127,7 +201,8 @@ async fn test_offer() {
         },
         create_mint::Params {},
     );
-    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![])
+    // Creating the mint again is a no-op
+    sign_send_instructions(&mut prg_test_ctx, vec![ix.clone(), ix], vec![])
         .await
         .unwrap();
 
@@ -136,11 +211,11 @@ async fn test_offer() {
     ////
 
     let (collection_mint, _) = Pubkey::find_program_address(
-        &[COLLECTION_PREFIX, &name_tokenizer::ID.to_bytes()],
-        &name_tokenizer::ID,
+        &[COLLECTION_PREFIX, &LOCALNET.program_id.to_bytes()],
+        &LOCALNET.program_id,
     );
     let central_state_collection_ata =
-        get_associated_token_address(&name_tokenizer::central_state::KEY, &collection_mint);
+        get_associated_token_address(&LOCALNET.central_state, &collection_mint);
 
     ////
     // Create collection
@@ -148,6 +223,7 @@ async fn test_offer() {
     let (edition_key, _) = MasterEdition::find_pda(&collection_mint);
     let (collection_metadata_key, _) = Metadata::find_pda(&collection_mint);
     let ix = create_collection(
+        LOCALNET.program_id,
         create_collection::Accounts {
             collection_mint: &collection_mint,
             edition: &edition_key,
@@ -161,11 +237,13 @@ async fn test_offer() {
             spl_name_service_program: &spl_name_service::ID,
             rent_account: &sysvar::rent::ID,
             ata_program: &spl_associated_token_account::ID,
+            tld_record: None,
         },
         create_collection::Params {},
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![])
+    // Creating the collection again is a no-op
+    sign_send_instructions(&mut prg_test_ctx, vec![ix.clone(), ix], vec![])
         .await
         .unwrap();
 
@@ -194,10 +272,12 @@ async fn test_offer() {
 
     let alice_nft_ata = get_associated_token_address(&alice.pubkey(), &nft_mint);
     let bob_nft_ata = get_associated_token_address(&bob.pubkey(), &nft_mint);
-    let (nft_record, _) = NftRecord::find_key(&name_key, &name_tokenizer::ID);
     let (metadata_key, _) = Metadata::find_pda(&nft_mint);
+    let (nft_edition_key, _) = MasterEdition::find_pda(&nft_mint);
+    let (nft_escrow, _) = LOCALNET.find_nft_escrow_key(&nft_mint);
 
     let ix = create_nft(
+        LOCALNET.program_id,
         create_nft::Accounts {
             mint: &nft_mint,
             nft_destination: &alice_nft_ata,
@@ -215,12 +295,26 @@ async fn test_offer() {
             edition_account: &edition_key,
             collection_metadata: &collection_metadata_key,
             collection_mint: &collection_mint,
-            #[cfg(not(feature = "devnet"))]
+            nft_edition: &nft_edition_key,
+            nft_escrow: &nft_escrow,
+            ata_program: &spl_associated_token_account::ID,
+            instructions_sysvar: &sysvar::instructions::ID,
             metadata_signer: &METADATA_SIGNER,
+            fee_source: &prg_test_ctx.payer.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            tld_record: None,
+            name_class: None,
+            parent_name: None,
+            parent_nft_record: None,
+            parent_nft_account: None,
         },
         create_nft::Params {
             name: name.to_string(),
             uri: "test".to_string(),
+            creator_share: 0,
+            soulbound: false,
+            wipe_data: false,
         },
     );
 
@@ -228,10 +322,67 @@ async fn test_offer() {
         .await
         .unwrap();
 
+    ////
+    // Lock NFT
+    ////
+    let staker = Keypair::new();
+    let (lock_record, _) = LockRecord::find_key(&alice_nft_ata, &LOCALNET.program_id);
+    let ix = set_lock_authority(
+        LOCALNET.program_id,
+        set_lock_authority::Accounts {
+            nft_owner: &alice.pubkey(),
+            nft_account: &alice_nft_ata,
+            nft_record: &nft_record,
+            name_account: &name_key,
+            lock_record: &lock_record,
+            central_state: &central_key,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            spl_token_program: &spl_token::ID,
+            system_program: &system_program::ID,
+        },
+        set_lock_authority::Params {
+            lock_authority: staker.pubkey(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    let set_lock_ix = |locked: bool| {
+        set_lock(
+            LOCALNET.program_id,
+            set_lock::Accounts {
+                lock_authority: &staker.pubkey(),
+                lock_record: &lock_record,
+                nft_record: &nft_record,
+                nft_account: &alice_nft_ata,
+                mint: &nft_mint,
+                nft_edition: &nft_edition_key,
+                central_state: &central_key,
+                spl_token_program: &spl_token::ID,
+                metadata_program: &mpl_token_metadata::ID,
+            },
+            set_lock::Params { locked },
+        )
+    };
+    sign_send_instructions(&mut prg_test_ctx, vec![set_lock_ix(true)], vec![&staker])
+        .await
+        .unwrap();
+
+    // Only the lock authority can lock the NFT
+    let mut ix = set_lock_ix(false);
+    ix.accounts[0].pubkey = alice.pubkey();
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+            .await
+            .is_err()
+    );
+
     ////
     // Edit data
     ////
     let ix = edit_data(
+        LOCALNET.program_id,
         name_tokenizer::instruction::edit_data::Accounts {
             nft_owner: &alice.pubkey(),
             nft_record: &nft_record,
@@ -239,6 +390,7 @@ async fn test_offer() {
             spl_token_program: &spl_token::ID,
             spl_name_service_program: &spl_name_service::ID,
             nft_account: &alice_nft_ata,
+            name_class: None,
         },
         edit_data::Params {
             offset: 0,
@@ -249,10 +401,166 @@ async fn test_offer() {
         .await
         .unwrap();
 
+    ////
+    // Resize, clear and write data
+    ////
+    let ix = manage_data(
+        LOCALNET.program_id,
+        manage_data::Accounts {
+            nft_owner: &alice.pubkey(),
+            nft_account: &alice_nft_ata,
+            nft_record: &nft_record,
+            name_account: &name_key,
+            spl_token_program: &spl_token::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            system_program: &system_program::ID,
+            name_class: None,
+        },
+        manage_data::Params {
+            space: Some(1200),
+            clears: vec![manage_data::DataRange { offset: 0, len: 1 }],
+            writes: vec![
+                manage_data::DataWrite {
+                    offset: 1100,
+                    data: vec![7, 7],
+                },
+                manage_data::DataWrite {
+                    offset: 10,
+                    data: vec![3],
+                },
+            ],
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(name_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let data = &info.data[spl_name_service::state::NameRecordHeader::LEN..];
+    assert_eq!(data.len(), 1200);
+    assert_eq!(data[0], 0);
+    assert_eq!(data[10], 3);
+    assert_eq!(&data[1100..1102], &[7, 7]);
+
+    // Out of bounds clears are rejected before their zeroes are allocated
+    for (offset, len) in [(0, 1201), (1, u32::MAX), (u32::MAX, 1)].iter() {
+        let ix = manage_data(
+            LOCALNET.program_id,
+            manage_data::Accounts {
+                nft_owner: &alice.pubkey(),
+                nft_account: &alice_nft_ata,
+                nft_record: &nft_record,
+                name_account: &name_key,
+                spl_token_program: &spl_token::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                fee_payer: &prg_test_ctx.payer.pubkey(),
+                system_program: &system_program::ID,
+                name_class: None,
+            },
+            manage_data::Params {
+                space: None,
+                clears: vec![manage_data::DataRange {
+                    offset: *offset,
+                    len: *len,
+                }],
+                writes: vec![],
+            },
+        );
+        let err = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+            .await
+            .is_err();
+        assert!(err);
+    }
+
+    // A locked NFT cannot be redeemed
+    let ix = redeem_nft(
+        LOCALNET.program_id,
+        redeem_nft::Accounts {
+            mint: &nft_mint,
+            nft_source: &alice_nft_ata,
+            nft_owner: &alice.pubkey(),
+            nft_record: &nft_record,
+            name_account: &name_key,
+            spl_token_program: &spl_token::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            nft_escrow: &nft_escrow,
+            central_state: &central_key,
+            system_program: &system_program::ID,
+            fee_source: &alice.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            nft_edition: &nft_edition_key,
+            metadata_program: &mpl_token_metadata::ID,
+            name_class: None,
+        },
+        redeem_nft::Params { wipe_data: false },
+    );
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+            .await
+            .is_err()
+    );
+
+    ////
+    // Unlock NFT
+    ////
+    sign_send_instructions(&mut prg_test_ctx, vec![set_lock_ix(false)], vec![&staker])
+        .await
+        .unwrap();
+    let ix = set_lock_authority(
+        LOCALNET.program_id,
+        set_lock_authority::Accounts {
+            nft_owner: &alice.pubkey(),
+            nft_account: &alice_nft_ata,
+            nft_record: &nft_record,
+            name_account: &name_key,
+            lock_record: &lock_record,
+            central_state: &central_key,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            spl_token_program: &spl_token::ID,
+            system_program: &system_program::ID,
+        },
+        set_lock_authority::Params {
+            lock_authority: Pubkey::default(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    // The lock authority granted again must not survive the redemption
+    let ix = set_lock_authority(
+        LOCALNET.program_id,
+        set_lock_authority::Accounts {
+            nft_owner: &alice.pubkey(),
+            nft_account: &alice_nft_ata,
+            nft_record: &nft_record,
+            name_account: &name_key,
+            lock_record: &lock_record,
+            central_state: &central_key,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            spl_token_program: &spl_token::ID,
+            system_program: &system_program::ID,
+        },
+        set_lock_authority::Params {
+            lock_authority: staker.pubkey(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
     ////
     // Withdraw NFT
     ////
     let ix = redeem_nft(
+        LOCALNET.program_id,
         redeem_nft::Accounts {
             mint: &nft_mint,
             nft_source: &alice_nft_ata,
@@ -261,10 +569,131 @@ async fn test_offer() {
             name_account: &name_key,
             spl_token_program: &spl_token::ID,
             spl_name_service_program: &spl_name_service::ID,
+            nft_escrow: &nft_escrow,
+            central_state: &central_key,
+            system_program: &system_program::ID,
+            fee_source: &alice.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            nft_edition: &nft_edition_key,
+            metadata_program: &mpl_token_metadata::ID,
+            name_class: None,
+        },
+        redeem_nft::Params { wipe_data: true },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    // The NFT is kept in escrow since its supply is capped by the master edition
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(nft_escrow)
+        .await
+        .unwrap()
+        .unwrap();
+    let escrow = spl_token::state::Account::unpack(&info.data).unwrap();
+    assert_eq!(escrow.amount, 1);
+
+    // The data written with edit_data is wiped on redemption
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(name_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(info.data[spl_name_service::state::NameRecordHeader::LEN..]
+        .iter()
+        .all(|b| *b == 0));
+
+    // The delegation backing the lock record is revoked
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(alice_nft_ata)
+        .await
+        .unwrap()
+        .unwrap();
+    let alice_nft = spl_token::state::Account::unpack(&info.data).unwrap();
+    assert!(alice_nft.delegate.is_none());
+
+    ////
+    // Alice tokenizes the domain again as soulbound in the same token account
+    ////
+    let ix = create_nft(
+        LOCALNET.program_id,
+        create_nft::Accounts {
+            mint: &nft_mint,
+            nft_destination: &alice_nft_ata,
+            name_account: &name_key,
+            nft_record: &nft_record,
+            name_owner: &alice.pubkey(),
+            metadata_account: &metadata_key,
+            central_state: &central_key,
+            spl_token_program: &spl_token::ID,
+            metadata_program: &mpl_token_metadata::ID,
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            rent_account: &sysvar::rent::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            edition_account: &edition_key,
+            collection_metadata: &collection_metadata_key,
+            collection_mint: &collection_mint,
+            nft_edition: &nft_edition_key,
+            nft_escrow: &nft_escrow,
+            ata_program: &spl_associated_token_account::ID,
+            instructions_sysvar: &sysvar::instructions::ID,
+            metadata_signer: &METADATA_SIGNER,
+            fee_source: &prg_test_ctx.payer.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            tld_record: None,
+            name_class: None,
+            parent_name: None,
+            parent_nft_record: None,
+            parent_nft_account: None,
+        },
+        create_nft::Params {
+            name: name.to_string(),
+            uri: "test".to_string(),
+            creator_share: 0,
+            soulbound: true,
+            wipe_data: false,
         },
-        redeem_nft::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+
+    // The stale lock authority cannot thaw the soulbound NFT
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![set_lock_ix(false)], vec![&staker])
+            .await
+            .is_err()
     );
 
+    let ix = redeem_nft(
+        LOCALNET.program_id,
+        redeem_nft::Accounts {
+            mint: &nft_mint,
+            nft_source: &alice_nft_ata,
+            nft_owner: &alice.pubkey(),
+            nft_record: &nft_record,
+            name_account: &name_key,
+            spl_token_program: &spl_token::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            nft_escrow: &nft_escrow,
+            central_state: &central_key,
+            system_program: &system_program::ID,
+            fee_source: &alice.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            nft_edition: &nft_edition_key,
+            metadata_program: &mpl_token_metadata::ID,
+            name_class: None,
+        },
+        redeem_nft::Params { wipe_data: false },
+    );
     sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
         .await
         .unwrap();
@@ -330,6 +759,7 @@ async fn test_offer() {
     ////
 
     let ix = withdraw_tokens(
+        LOCALNET.program_id,
         withdraw_tokens::Accounts {
             nft: &alice_nft_ata,
             nft_owner: &alice.pubkey(),
@@ -350,6 +780,7 @@ async fn test_offer() {
     ////
 
     let ix = withdraw_tokens(
+        LOCALNET.program_id,
         withdraw_tokens::Accounts {
             nft: &alice_nft_ata,
             nft_owner: &bob.pubkey(),
@@ -385,6 +816,7 @@ async fn test_offer() {
     // Bob creates NFT again
     ////
     let ix = create_nft(
+        LOCALNET.program_id,
         create_nft::Accounts {
             mint: &nft_mint,
             nft_destination: &bob_nft_ata,
@@ -402,63 +834,314 @@ async fn test_offer() {
             edition_account: &edition_key,
             collection_metadata: &collection_metadata_key,
             collection_mint: &collection_mint,
-            #[cfg(not(feature = "devnet"))]
+            nft_edition: &nft_edition_key,
+            nft_escrow: &nft_escrow,
+            ata_program: &spl_associated_token_account::ID,
+            instructions_sysvar: &sysvar::instructions::ID,
             metadata_signer: &METADATA_SIGNER,
+            fee_source: &prg_test_ctx.payer.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            tld_record: None,
+            name_class: None,
+            parent_name: None,
+            parent_nft_record: None,
+            parent_nft_account: None,
         },
         create_nft::Params {
             name: name.to_string(),
             uri: "test".to_string(),
+            creator_share: 0,
+            soulbound: false,
+            wipe_data: false,
         },
     );
     sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&bob])
         .await
         .unwrap();
-    let ix = withdraw_tokens(
-        withdraw_tokens::Accounts {
-            nft: &bob_nft_ata,
-            nft_owner: &bob.pubkey(),
+
+    ////
+    // Anyone syncs the record owner with the NFT holder after a trade
+    ////
+    for (from, to, holder) in [(&bob, alice_nft_ata, &alice), (&alice, bob_nft_ata, &bob)] {
+        let source = get_associated_token_address(&from.pubkey(), &nft_mint);
+        let ix =
+            spl_token::instruction::transfer(&spl_token::ID, &source, &to, &from.pubkey(), &[], 1)
+                .unwrap();
+        let ix_sync = sync_owner(
+            LOCALNET.program_id,
+            sync_owner::Accounts {
+                nft_record: &nft_record,
+                nft_account: &to,
+            },
+            sync_owner::Params {},
+        );
+        sign_send_instructions(&mut prg_test_ctx, vec![ix, ix_sync], vec![from])
+            .await
+            .unwrap();
+        let info = prg_test_ctx
+            .banks_client
+            .get_account(nft_record)
+            .await
+            .unwrap()
+            .unwrap();
+        let record = NftRecord::deserialize(&mut info.data.as_slice()).unwrap();
+        assert_eq!(record.owner, holder.pubkey());
+    }
+
+    ////
+    // Programs read the effective owner of the domain
+    ////
+    let ix = get_effective_owner(
+        LOCALNET.program_id,
+        get_effective_owner::Accounts {
+            name_account: &name_key,
             nft_record: &nft_record,
-            token_source: &usdc_ata_program,
-            token_destination: &usdc_ata_bob,
-            spl_token_program: &spl_token::ID,
-            system_program: &system_program::ID,
+            nft_account: Some(&bob_nft_ata),
         },
-        withdraw_tokens::Params {},
+        get_effective_owner::Params {},
     );
-    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&bob])
-        .await
-        .unwrap();
-
-    //////
-    // Unverify NFT
-    //////
-    let info = prg_test_ctx
-        .banks_client
-        .get_account(metadata_key)
+    let data = simulate_return_data(&mut prg_test_ctx, vec![ix])
         .await
         .unwrap()
         .unwrap();
+    assert_eq!(
+        get_effective_owner::EffectiveOwner::try_from_slice(&data).unwrap(),
+        get_effective_owner::EffectiveOwner {
+            owner: bob.pubkey(),
+            tokenized: true,
+        }
+    );
 
-    let des = Metadata::safe_deserialize(&info.data).unwrap();
-    assert!(des.collection.unwrap().verified);
+    // Any other record is rejected, instead of reporting the name owner as untokenized
+    let ix = get_effective_owner(
+        LOCALNET.program_id,
+        get_effective_owner::Accounts {
+            name_account: &name_key,
+            nft_record: &Pubkey::new_unique(),
+            nft_account: Some(&bob_nft_ata),
+        },
+        get_effective_owner::Params {},
+    );
+    simulate_return_data(&mut prg_test_ctx, vec![ix])
+        .await
+        .unwrap_err();
 
-    let ix = unverify_nft(
-        unverify_nft::Accounts {
-            metadata_account: &metadata_key,
-            edition_account: &edition_key,
-            collection_metadata: &collection_metadata_key,
-            collection_mint: &collection_mint,
+    ////
+    // Bob tokenizes a subdomain of Alice as the holder of the parent NFT
+    ////
+    let (sub_mint, _) =
+        Pubkey::find_program_address(&[MINT_PREFIX, &sub_key.to_bytes()], &LOCALNET.program_id);
+    let ix = create_mint(
+        LOCALNET.program_id,
+        create_mint::Accounts {
+            mint: &sub_mint,
             central_state: &central_key,
+            name_account: &sub_key,
+            spl_token_program: &spl_token::ID,
+            rent_account: &sysvar::rent::ID,
             fee_payer: &prg_test_ctx.payer.pubkey(),
-            metadata_program: &mpl_token_metadata::ID,
             system_program: &system_program::ID,
-            rent_account: &sysvar::rent::ID,
-            #[cfg(not(feature = "devnet"))]
-            metadata_signer: &METADATA_SIGNER,
         },
-        unverify_nft::Params {},
+        create_mint::Params {},
     );
-    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![])
+    let ix_ata =
+        create_associated_token_account(&bob.pubkey(), &bob.pubkey(), &sub_mint, &spl_token::ID);
+    sign_send_instructions(&mut prg_test_ctx, vec![ix, ix_ata], vec![&bob])
+        .await
+        .unwrap();
+
+    let (sub_nft_record, _) = NftRecord::find_key(&sub_key, &LOCALNET.program_id);
+    let (sub_metadata_key, _) = Metadata::find_pda(&sub_mint);
+    let (sub_edition_key, _) = MasterEdition::find_pda(&sub_mint);
+    let ix = create_nft(
+        LOCALNET.program_id,
+        create_nft::Accounts {
+            mint: &sub_mint,
+            nft_destination: &get_associated_token_address(&bob.pubkey(), &sub_mint),
+            name_account: &sub_key,
+            nft_record: &sub_nft_record,
+            name_owner: &bob.pubkey(),
+            metadata_account: &sub_metadata_key,
+            central_state: &central_key,
+            spl_token_program: &spl_token::ID,
+            metadata_program: &mpl_token_metadata::ID,
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            rent_account: &sysvar::rent::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            edition_account: &edition_key,
+            collection_metadata: &collection_metadata_key,
+            collection_mint: &collection_mint,
+            nft_edition: &sub_edition_key,
+            nft_escrow: &LOCALNET.find_nft_escrow_key(&sub_mint).0,
+            ata_program: &spl_associated_token_account::ID,
+            instructions_sysvar: &sysvar::instructions::ID,
+            metadata_signer: &METADATA_SIGNER,
+            fee_source: &prg_test_ctx.payer.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            tld_record: None,
+            name_class: None,
+            parent_name: Some(&name_key),
+            parent_nft_record: Some(&nft_record),
+            parent_nft_account: Some(&bob_nft_ata),
+        },
+        create_nft::Params {
+            name: format!("sub.{}", name),
+            uri: "test".to_string(),
+            creator_share: 0,
+            soulbound: false,
+            wipe_data: false,
+        },
+    );
+    // The absent TLD record and name class are passed as the program ID
+    assert_eq!(ix.accounts[24].pubkey, LOCALNET.program_id);
+    assert_eq!(
+        ix.accounts[25],
+        AccountMeta::new_readonly(LOCALNET.program_id, false)
+    );
+
+    // Placeholders are rejected: the uninitialized .sol TLD record, and the name owner standing
+    // in for the class of a subdomain without one
+    let (root_tld_record, _) = TldRecord::find_key(&ROOT_DOMAIN_ACCOUNT, &LOCALNET.program_id);
+    let mut placeholders = ix.clone();
+    placeholders.accounts[24].pubkey = root_tld_record;
+    let err = sign_send_instructions(&mut prg_test_ctx, vec![placeholders], vec![&bob])
+        .await
+        .is_err();
+    assert!(err);
+    let mut placeholders = ix.clone();
+    placeholders.accounts[25] = AccountMeta::new_readonly(bob.pubkey(), true);
+    let err = sign_send_instructions(&mut prg_test_ctx, vec![placeholders], vec![&bob])
+        .await
+        .is_err();
+    assert!(err);
+
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&bob])
+        .await
+        .unwrap();
+
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(sub_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = spl_name_service::state::NameRecordHeader::unpack_from_slice(&info.data).unwrap();
+    assert_eq!(header.owner, sub_nft_record);
+
+    ////
+    // Bob tokenizes another subdomain of Alice in a single instruction
+    ////
+    let (sub2_mint, _) =
+        Pubkey::find_program_address(&[MINT_PREFIX, &sub2_key.to_bytes()], &LOCALNET.program_id);
+    let (sub2_nft_record, _) = NftRecord::find_key(&sub2_key, &LOCALNET.program_id);
+    let (sub2_metadata_key, _) = Metadata::find_pda(&sub2_mint);
+    let (sub2_edition_key, _) = MasterEdition::find_pda(&sub2_mint);
+    // The mint and the token account of Bob are created along with the NFT
+    let ix = create_mint_and_nft(
+        LOCALNET.program_id,
+        create_mint_and_nft::Accounts {
+            mint: &sub2_mint,
+            nft_destination: &get_associated_token_address(&bob.pubkey(), &sub2_mint),
+            name_account: &sub2_key,
+            nft_record: &sub2_nft_record,
+            name_owner: &bob.pubkey(),
+            metadata_account: &sub2_metadata_key,
+            central_state: &central_key,
+            spl_token_program: &spl_token::ID,
+            metadata_program: &mpl_token_metadata::ID,
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            rent_account: &sysvar::rent::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            edition_account: &edition_key,
+            collection_metadata: &collection_metadata_key,
+            collection_mint: &collection_mint,
+            nft_edition: &sub2_edition_key,
+            nft_escrow: &LOCALNET.find_nft_escrow_key(&sub2_mint).0,
+            ata_program: &spl_associated_token_account::ID,
+            instructions_sysvar: &sysvar::instructions::ID,
+            metadata_signer: &METADATA_SIGNER,
+            fee_source: &prg_test_ctx.payer.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            tld_record: None,
+            name_class: None,
+            parent_name: Some(&name_key),
+            parent_nft_record: Some(&nft_record),
+            parent_nft_account: Some(&bob_nft_ata),
+        },
+        create_mint_and_nft::Params {
+            name: format!("sub2.{}", name),
+            uri: "test".to_string(),
+            creator_share: 0,
+            soulbound: false,
+            wipe_data: false,
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&bob])
+        .await
+        .unwrap();
+
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(sub2_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = spl_name_service::state::NameRecordHeader::unpack_from_slice(&info.data).unwrap();
+    assert_eq!(header.owner, sub2_nft_record);
+
+    let ix = withdraw_tokens(
+        LOCALNET.program_id,
+        withdraw_tokens::Accounts {
+            nft: &bob_nft_ata,
+            nft_owner: &bob.pubkey(),
+            nft_record: &nft_record,
+            token_source: &usdc_ata_program,
+            token_destination: &usdc_ata_bob,
+            spl_token_program: &spl_token::ID,
+            system_program: &system_program::ID,
+        },
+        withdraw_tokens::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&bob])
+        .await
+        .unwrap();
+
+    //////
+    // Unverify NFT
+    //////
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(metadata_key)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let des = Metadata::safe_deserialize(&info.data).unwrap();
+    assert!(des.collection.unwrap().verified);
+
+    let ix = unverify_nft(
+        LOCALNET.program_id,
+        unverify_nft::Accounts {
+            metadata_account: &metadata_key,
+            edition_account: &edition_key,
+            collection_metadata: &collection_metadata_key,
+            collection_mint: &collection_mint,
+            central_state: &central_key,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            metadata_program: &mpl_token_metadata::ID,
+            system_program: &system_program::ID,
+            rent_account: &sysvar::rent::ID,
+            metadata_signer: &METADATA_SIGNER,
+            tld_record: None,
+        },
+        unverify_nft::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![])
         .await
         .unwrap();
     let info = prg_test_ctx
@@ -471,3 +1154,1185 @@ async fn test_offer() {
     let des = Metadata::safe_deserialize(&info.data).unwrap();
     assert!(!des.collection.unwrap().verified);
 }
+
+#[tokio::test]
+async fn test_admin() {
+    // Create program and test environment
+    let alice = Keypair::new();
+    let admin = Keypair::new();
+    let class = Keypair::new();
+    let referrer = Keypair::new();
+
+    let mut program_test = ProgramTest::new(
+        "name_tokenizer",
+        LOCALNET.program_id,
+        processor!(process_instruction),
+    );
+    program_test.add_program("spl_name_service", spl_name_service::ID, None);
+    program_test.add_program("mpl_token_metadata", mpl_token_metadata::ID, None);
+    let forwarder = Pubkey::new_unique();
+    program_test.add_program("forwarder", forwarder, processor!(process_forward));
+    let program_data = program_data_bootstrap(&mut program_test, &admin.pubkey());
+
+    // Create TLD and domain name
+    let tld = Pubkey::new_unique();
+    program_test.add_account(
+        tld,
+        Account {
+            lamports: 1_000_000,
+            data: spl_name_service::state::NameRecordHeader {
+                parent_name: Pubkey::default(),
+                owner: admin.pubkey(),
+                class: Pubkey::default(),
+            }
+            .try_to_vec()
+            .unwrap(),
+            owner: spl_name_service::id(),
+            ..Account::default()
+        },
+    );
+
+    let name = "something_domain_name";
+    let hashed_name = hashv(&[(HASH_PREFIX.to_owned() + name).as_bytes()])
+        .as_ref()
+        .to_vec();
+    let (name_key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        hashed_name,
+        Some(&class.pubkey()),
+        Some(&tld),
+    );
+
+    let name_domain_data = [
+        spl_name_service::state::NameRecordHeader {
+            parent_name: tld,
+            owner: alice.pubkey(),
+            class: class.pubkey(),
+        }
+        .try_to_vec()
+        .unwrap(),
+        vec![0; 1000],
+    ]
+    .concat();
+    program_test.add_account(
+        name_key,
+        Account {
+            lamports: 1_000_000,
+            data: name_domain_data,
+            owner: spl_name_service::id(),
+            ..Account::default()
+        },
+    );
+    program_test.add_account(
+        alice.pubkey(),
+        Account {
+            lamports: 100_000_000_000,
+            ..Account::default()
+        },
+    );
+
+    // A record of the program for the same domain, at another key than its PDA
+    let forged_record = Pubkey::new_unique();
+    let (forged_mint, _) =
+        Pubkey::find_program_address(&[MINT_PREFIX, &name_key.to_bytes()], &LOCALNET.program_id);
+    program_test.add_account(
+        forged_record,
+        Account {
+            lamports: 1_000_000,
+            data: NftRecord::new(
+                NftRecord::find_key(&name_key, &LOCALNET.program_id).1,
+                alice.pubkey(),
+                name_key,
+                forged_mint,
+                class.pubkey(),
+                false,
+                NftRecordNonces::default(),
+            )
+            .try_to_vec()
+            .unwrap(),
+            owner: LOCALNET.program_id,
+            ..Account::default()
+        },
+    );
+
+    let mint_authority = Keypair::new();
+    let (token_mint, _) = mint_bootstrap(None, 6, &mut program_test, &mint_authority.pubkey());
+
+    let mut prg_test_ctx = program_test.start_with_context().await;
+    let (central_key, _) = CentralState::find_key(&LOCALNET.program_id);
+
+    ////
+    // Register TLD
+    ////
+    let (tld_record, _) = TldRecord::find_key(&tld, &LOCALNET.program_id);
+    let ix = register_tld(
+        LOCALNET.program_id,
+        register_tld::Accounts {
+            tld_record: &tld_record,
+            tld: &tld,
+            program_data: &program_data,
+            admin: &admin.pubkey(),
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            system_program: &system_program::ID,
+        },
+        register_tld::Params {
+            collection_name: "Test TLD collection".to_string(),
+            collection_uri: "test".to_string(),
+            symbol: ".test".to_string(),
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&admin])
+        .await
+        .unwrap();
+
+    ////
+    // Configure derived URIs
+    ////
+    let ix = update_central_state(
+        LOCALNET.program_id,
+        update_central_state::Accounts {
+            central_state: &central_key,
+            program_data: &program_data,
+            admin: &admin.pubkey(),
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            system_program: &system_program::ID,
+        },
+        update_central_state::Params {
+            base_uri: "https://example.com/".to_string(),
+            uri_derivation: UriDerivation::Mint,
+            max_creator_share: 10,
+            fees: Fees {
+                mint: Pubkey::default(),
+                tokenization_fee: 2_000_000,
+                redemption_fee: 0,
+                referrer_share: 50,
+            },
+            allowed_programs: vec![spl_token::ID, forwarder],
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&admin])
+        .await
+        .unwrap();
+
+    ////
+    // Create TLD collection
+    ////
+    let (collection_mint, _) = TldRecord::find_collection_key(&tld, &LOCALNET.program_id);
+    let central_state_collection_ata =
+        get_associated_token_address(&LOCALNET.central_state, &collection_mint);
+    let (edition_key, _) = MasterEdition::find_pda(&collection_mint);
+    let (collection_metadata_key, _) = Metadata::find_pda(&collection_mint);
+    let ix = create_collection(
+        LOCALNET.program_id,
+        create_collection::Accounts {
+            collection_mint: &collection_mint,
+            edition: &edition_key,
+            metadata_account: &collection_metadata_key,
+            central_state: &central_key,
+            central_state_nft_ata: &central_state_collection_ata,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            spl_token_program: &spl_token::ID,
+            metadata_program: &mpl_token_metadata::ID,
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            rent_account: &sysvar::rent::ID,
+            ata_program: &spl_associated_token_account::ID,
+            tld_record: Some(&tld_record),
+        },
+        create_collection::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![])
+        .await
+        .unwrap();
+
+    ////
+    // Create mint and NFT
+    ////
+    let (nft_mint, _) =
+        Pubkey::find_program_address(&[MINT_PREFIX, &name_key.to_bytes()], &LOCALNET.program_id);
+    let ix = create_mint(
+        LOCALNET.program_id,
+        create_mint::Accounts {
+            mint: &nft_mint,
+            central_state: &central_key,
+            name_account: &name_key,
+            spl_token_program: &spl_token::ID,
+            rent_account: &sysvar::rent::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            system_program: &system_program::ID,
+        },
+        create_mint::Params {},
+    );
+    let alice_nft_ata = get_associated_token_address(&alice.pubkey(), &nft_mint);
+    let ix_ata = create_associated_token_account(
+        &alice.pubkey(),
+        &alice.pubkey(),
+        &nft_mint,
+        &spl_token::ID,
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix, ix_ata], vec![&alice])
+        .await
+        .unwrap();
+
+    let (nft_record, _) = NftRecord::find_key(&name_key, &LOCALNET.program_id);
+    let (metadata_key, _) = Metadata::find_pda(&nft_mint);
+    let (nft_edition_key, _) = MasterEdition::find_pda(&nft_mint);
+    let (nft_escrow, _) = LOCALNET.find_nft_escrow_key(&nft_mint);
+    let ix = create_nft(
+        LOCALNET.program_id,
+        create_nft::Accounts {
+            mint: &nft_mint,
+            nft_destination: &alice_nft_ata,
+            name_account: &name_key,
+            nft_record: &nft_record,
+            name_owner: &alice.pubkey(),
+            metadata_account: &metadata_key,
+            central_state: &central_key,
+            spl_token_program: &spl_token::ID,
+            metadata_program: &mpl_token_metadata::ID,
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            rent_account: &sysvar::rent::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            edition_account: &edition_key,
+            collection_metadata: &collection_metadata_key,
+            collection_mint: &collection_mint,
+            nft_edition: &nft_edition_key,
+            nft_escrow: &nft_escrow,
+            ata_program: &spl_associated_token_account::ID,
+            instructions_sysvar: &sysvar::instructions::ID,
+            metadata_signer: &METADATA_SIGNER,
+            fee_source: &prg_test_ctx.payer.pubkey(),
+            fee_vault: &central_key,
+            referrer: &referrer.pubkey(),
+            tld_record: Some(&tld_record),
+            name_class: Some(&class.pubkey()),
+            parent_name: None,
+            parent_nft_record: None,
+            parent_nft_account: None,
+        },
+        create_nft::Params {
+            name: name.to_string(),
+            uri: String::new(),
+            creator_share: 5,
+            soulbound: true,
+            wipe_data: true,
+        },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice, &class])
+        .await
+        .unwrap();
+
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(nft_record)
+        .await
+        .unwrap()
+        .unwrap();
+    let record = NftRecord::deserialize(&mut info.data.as_slice()).unwrap();
+    assert_eq!(record.class, class.pubkey());
+    assert!(record.soulbound);
+
+    // The class of the domain must sign edits of its data
+    for (name_class, succeeds) in [(None, false), (Some(&class.pubkey()), true)] {
+        let ix = edit_data(
+            LOCALNET.program_id,
+            edit_data::Accounts {
+                nft_owner: &alice.pubkey(),
+                nft_account: &alice_nft_ata,
+                nft_record: &nft_record,
+                name_account: &name_key,
+                spl_token_program: &spl_token::ID,
+                spl_name_service_program: &spl_name_service::ID,
+                name_class,
+            },
+            edit_data::Params {
+                offset: 0,
+                data: vec![1, 2, 3],
+            },
+        );
+        let signers = if succeeds {
+            vec![&alice, &class]
+        } else {
+            vec![&alice]
+        };
+        let result = sign_send_instructions(&mut prg_test_ctx, vec![ix], signers).await;
+        assert_eq!(result.is_ok(), succeeds);
+    }
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(name_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        &info.data[NameRecordHeader::LEN..NameRecordHeader::LEN + 3],
+        &[1, 2, 3]
+    );
+
+    // Soulbound NFTs cannot be transferred
+    let admin_nft_ata = get_associated_token_address(&admin.pubkey(), &nft_mint);
+    let ix_ata = create_associated_token_account(
+        &prg_test_ctx.payer.pubkey(),
+        &admin.pubkey(),
+        &nft_mint,
+        &spl_token::ID,
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix_ata], vec![])
+        .await
+        .unwrap();
+    let ix = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &alice_nft_ata,
+        &admin_nft_ata,
+        &alice.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap_err();
+
+    ////
+    // The NFT holder acts through the NFT record
+    ////
+    let record_token_ata = get_associated_token_address(&nft_record, &token_mint);
+    let alice_token_ata = get_associated_token_address(&alice.pubkey(), &token_mint);
+    let ixs = vec![
+        create_associated_token_account(
+            &prg_test_ctx.payer.pubkey(),
+            &nft_record,
+            &token_mint,
+            &spl_token::ID,
+        ),
+        create_associated_token_account(
+            &prg_test_ctx.payer.pubkey(),
+            &alice.pubkey(),
+            &token_mint,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::mint_to(
+            &spl_token::ID,
+            &token_mint,
+            &record_token_ata,
+            &mint_authority.pubkey(),
+            &[],
+            10,
+        )
+        .unwrap(),
+    ];
+    sign_send_instructions(&mut prg_test_ctx, ixs, vec![&mint_authority])
+        .await
+        .unwrap();
+
+    let execute_accounts = execute::Accounts {
+        nft_owner: &alice.pubkey(),
+        nft_account: &alice_nft_ata,
+        nft_record: &nft_record,
+        central_state: &central_key,
+        target_program: &spl_token::ID,
+    };
+    let inner = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &record_token_ata,
+        &alice_token_ata,
+        &nft_record,
+        &[],
+        4,
+    )
+    .unwrap();
+    let ix = execute(LOCALNET.program_id, execute_accounts, inner);
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(alice_token_ata)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        spl_token::state::Account::unpack(&info.data)
+            .unwrap()
+            .amount,
+        4
+    );
+
+    // The record must be the PDA of its domain, even for calls it does not sign
+    let inner = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &alice_token_ata,
+        &record_token_ata,
+        &alice.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    let ix = execute(
+        LOCALNET.program_id,
+        execute::Accounts {
+            nft_owner: &alice.pubkey(),
+            nft_account: &alice_nft_ata,
+            nft_record: &forged_record,
+            central_state: &central_key,
+            target_program: &spl_token::ID,
+        },
+        inner,
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap_err();
+
+    // The domain cannot leave the record through the name service
+    let inner = spl_name_service::instruction::transfer(
+        spl_name_service::ID,
+        alice.pubkey(),
+        name_key,
+        nft_record,
+        Some(class.pubkey()),
+    )
+    .unwrap();
+    let ix = execute(
+        LOCALNET.program_id,
+        execute::Accounts {
+            nft_owner: &alice.pubkey(),
+            nft_account: &alice_nft_ata,
+            nft_record: &nft_record,
+            central_state: &central_key,
+            target_program: &spl_name_service::ID,
+        },
+        inner,
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice, &class])
+        .await
+        .unwrap_err();
+
+    // Nor through an allowed program relaying the call to the name service
+    let mut inner = spl_name_service::instruction::transfer(
+        spl_name_service::ID,
+        alice.pubkey(),
+        name_key,
+        nft_record,
+        Some(class.pubkey()),
+    )
+    .unwrap();
+    inner
+        .accounts
+        .insert(0, AccountMeta::new_readonly(spl_name_service::ID, false));
+    inner.program_id = forwarder;
+    let ix = execute(
+        LOCALNET.program_id,
+        execute::Accounts {
+            nft_owner: &alice.pubkey(),
+            nft_account: &alice_nft_ata,
+            nft_record: &nft_record,
+            central_state: &central_key,
+            target_program: &forwarder,
+        },
+        inner,
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice, &class])
+        .await
+        .unwrap_err();
+
+    ////
+    // The tokenization fee is split between the referrer and the fee vault
+    ////
+    let balance = prg_test_ctx
+        .banks_client
+        .get_balance(referrer.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(balance, 1_000_000);
+
+    let treasury = Pubkey::new_unique();
+    let ix = withdraw_fees(
+        LOCALNET.program_id,
+        withdraw_fees::Accounts {
+            central_state: &central_key,
+            fee_vault: &central_key,
+            destination: &treasury,
+            program_data: &program_data,
+            admin: &admin.pubkey(),
+            spl_token_program: &spl_token::ID,
+            fee_vault_authority: None,
+        },
+        withdraw_fees::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&admin])
+        .await
+        .unwrap();
+    let balance = prg_test_ctx
+        .banks_client
+        .get_balance(treasury)
+        .await
+        .unwrap();
+    assert_eq!(balance, 1_000_000);
+
+    // Token accounts of the central state, e.g the NFT escrows, are not fee vaults
+    let (collection_fee_authority, _) = LOCALNET.find_fee_vault_authority(&collection_mint);
+    let admin_collection_ata = get_associated_token_address(&admin.pubkey(), &collection_mint);
+    let ix_ata = create_associated_token_account(
+        &prg_test_ctx.payer.pubkey(),
+        &admin.pubkey(),
+        &collection_mint,
+        &spl_token::ID,
+    );
+    let ix = withdraw_fees(
+        LOCALNET.program_id,
+        withdraw_fees::Accounts {
+            central_state: &central_key,
+            fee_vault: &central_state_collection_ata,
+            destination: &admin_collection_ata,
+            program_data: &program_data,
+            admin: &admin.pubkey(),
+            spl_token_program: &spl_token::ID,
+            fee_vault_authority: Some(&collection_fee_authority),
+        },
+        withdraw_fees::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix_ata.clone(), ix], vec![&admin])
+        .await
+        .unwrap_err();
+
+    // Vaults of mints other than the current fee mint remain withdrawable
+    let collection_fee_vault = LOCALNET.find_fee_vault_key(&collection_mint);
+    let ix_vault = create_associated_token_account(
+        &prg_test_ctx.payer.pubkey(),
+        &collection_fee_authority,
+        &collection_mint,
+        &spl_token::ID,
+    );
+    let ix = withdraw_fees(
+        LOCALNET.program_id,
+        withdraw_fees::Accounts {
+            central_state: &central_key,
+            fee_vault: &collection_fee_vault,
+            destination: &admin_collection_ata,
+            program_data: &program_data,
+            admin: &admin.pubkey(),
+            spl_token_program: &spl_token::ID,
+            fee_vault_authority: Some(&collection_fee_authority),
+        },
+        withdraw_fees::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix_ata, ix_vault, ix], vec![&admin])
+        .await
+        .unwrap();
+
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(metadata_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let des = Metadata::safe_deserialize(&info.data).unwrap();
+    assert_eq!(des.symbol.trim_end_matches('\0'), ".test");
+    assert_eq!(des.collection.unwrap().key, collection_mint);
+    assert_eq!(
+        des.uri.trim_end_matches('\0'),
+        format!("https://example.com/{}", nft_mint)
+    );
+    let creators = des.creators.unwrap();
+    assert_eq!(creators[1].share, 95);
+    assert_eq!(creators[2].address, alice.pubkey());
+    assert_eq!(creators[2].share, 5);
+
+    ////
+    // Alice adjusts the royalty split within the central state limit
+    ////
+    let royalties = Keypair::new();
+    for (share, succeeds) in [(11, false), (10, true)] {
+        let ix = edit_creators(
+            LOCALNET.program_id,
+            edit_creators::Accounts {
+                nft_owner: &alice.pubkey(),
+                nft_account: &alice_nft_ata,
+                nft_record: &nft_record,
+                name_account: &name_key,
+                metadata_account: &metadata_key,
+                central_state: &central_key,
+                metadata_program: &mpl_token_metadata::ID,
+            },
+            edit_creators::Params {
+                creator: royalties.pubkey(),
+                share,
+            },
+        );
+        let result = sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice]).await;
+        assert_eq!(result.is_ok(), succeeds);
+    }
+
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(metadata_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let des = Metadata::safe_deserialize(&info.data).unwrap();
+    let creators = des.creators.unwrap();
+    assert_eq!(creators[1].share, 90);
+    assert_eq!(creators[2].address, royalties.pubkey());
+    assert_eq!(creators[2].share, 10);
+
+    ////
+    // Redeem NFT, the class of the domain must sign
+    ////
+    let ix = redeem_nft(
+        LOCALNET.program_id,
+        redeem_nft::Accounts {
+            mint: &nft_mint,
+            nft_source: &alice_nft_ata,
+            nft_owner: &alice.pubkey(),
+            nft_record: &nft_record,
+            name_account: &name_key,
+            spl_token_program: &spl_token::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            nft_escrow: &nft_escrow,
+            central_state: &central_key,
+            system_program: &system_program::ID,
+            fee_source: &alice.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            nft_edition: &nft_edition_key,
+            metadata_program: &mpl_token_metadata::ID,
+            name_class: None,
+        },
+        redeem_nft::Params { wipe_data: false },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap_err();
+
+    let ix = redeem_nft(
+        LOCALNET.program_id,
+        redeem_nft::Accounts {
+            mint: &nft_mint,
+            nft_source: &alice_nft_ata,
+            nft_owner: &alice.pubkey(),
+            nft_record: &nft_record,
+            name_account: &name_key,
+            spl_token_program: &spl_token::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            nft_escrow: &nft_escrow,
+            central_state: &central_key,
+            system_program: &system_program::ID,
+            fee_source: &alice.pubkey(),
+            fee_vault: &central_key,
+            referrer: &central_key,
+            nft_edition: &nft_edition_key,
+            metadata_program: &mpl_token_metadata::ID,
+            name_class: Some(&class.pubkey()),
+        },
+        redeem_nft::Params { wipe_data: false },
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&alice, &class])
+        .await
+        .unwrap();
+
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(name_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = spl_name_service::state::NameRecordHeader::unpack_from_slice(&info.data).unwrap();
+    assert_eq!(header.owner, alice.pubkey());
+}
+
+#[tokio::test]
+async fn test_attested_uri() {
+    // Create program and test environment
+    let program_id = LOCALNET_ATTESTED.program_id;
+    let alice = Keypair::new();
+    let metadata_signer = attested_metadata_signer();
+
+    let mut program_test = ProgramTest::new(
+        "name_tokenizer",
+        program_id,
+        processor!(process_instruction),
+    );
+    program_test.add_program("spl_name_service", spl_name_service::ID, None);
+    program_test.add_program("mpl_token_metadata", mpl_token_metadata::ID, None);
+
+    // Create domain name
+    let name = "attested_domain_name";
+    let hashed_name = hashv(&[(HASH_PREFIX.to_owned() + name).as_bytes()])
+        .as_ref()
+        .to_vec();
+    let (name_key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        hashed_name,
+        None,
+        Some(&ROOT_DOMAIN_ACCOUNT),
+    );
+    program_test.add_account(
+        name_key,
+        Account {
+            lamports: 1_000_000,
+            data: NameRecordHeader {
+                parent_name: ROOT_DOMAIN_ACCOUNT,
+                owner: alice.pubkey(),
+                class: Pubkey::default(),
+            }
+            .try_to_vec()
+            .unwrap(),
+            owner: spl_name_service::id(),
+            ..Account::default()
+        },
+    );
+    program_test.add_account(
+        alice.pubkey(),
+        Account {
+            lamports: 100_000_000_000,
+            ..Account::default()
+        },
+    );
+
+    let mut prg_test_ctx = program_test.start_with_context().await;
+
+    ////
+    // Create mint and collection
+    ////
+    let central_key = LOCALNET_ATTESTED.central_state;
+    let (nft_mint, _) =
+        Pubkey::find_program_address(&[MINT_PREFIX, &name_key.to_bytes()], &program_id);
+    let ix = create_mint(
+        program_id,
+        create_mint::Accounts {
+            mint: &nft_mint,
+            central_state: &central_key,
+            name_account: &name_key,
+            spl_token_program: &spl_token::ID,
+            rent_account: &sysvar::rent::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            system_program: &system_program::ID,
+        },
+        create_mint::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![])
+        .await
+        .unwrap();
+
+    let collection_mint = LOCALNET_ATTESTED.collection_mint;
+    let (edition_key, _) = MasterEdition::find_pda(&collection_mint);
+    let (collection_metadata_key, _) = Metadata::find_pda(&collection_mint);
+    let ix = create_collection(
+        program_id,
+        create_collection::Accounts {
+            collection_mint: &collection_mint,
+            edition: &edition_key,
+            metadata_account: &collection_metadata_key,
+            central_state: &central_key,
+            central_state_nft_ata: &get_associated_token_address(&central_key, &collection_mint),
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            spl_token_program: &spl_token::ID,
+            metadata_program: &mpl_token_metadata::ID,
+            system_program: &system_program::ID,
+            spl_name_service_program: &spl_name_service::ID,
+            rent_account: &sysvar::rent::ID,
+            ata_program: &spl_associated_token_account::ID,
+            tld_record: None,
+        },
+        create_collection::Params {},
+    );
+    let ix_ata = create_associated_token_account(
+        &alice.pubkey(),
+        &alice.pubkey(),
+        &nft_mint,
+        &spl_token::ID,
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![ix, ix_ata], vec![&alice])
+        .await
+        .unwrap();
+
+    ////
+    // Create NFT with an attested URI
+    ////
+    let uri = "https://example.com/attested.json";
+    let (nft_record, _) = NftRecord::find_key(&name_key, &program_id);
+    let (metadata_key, _) = Metadata::find_pda(&nft_mint);
+    let (nft_edition_key, _) = MasterEdition::find_pda(&nft_mint);
+    let (nft_escrow, _) = LOCALNET_ATTESTED.find_nft_escrow_key(&nft_mint);
+    let payer = prg_test_ctx.payer.pubkey();
+    let metadata_signer_key = metadata_signer.pubkey();
+    let alice_key = alice.pubkey();
+    let alice_nft_ata = get_associated_token_address(&alice_key, &nft_mint);
+    let accounts = || create_nft::Accounts {
+        mint: &nft_mint,
+        nft_destination: &alice_nft_ata,
+        name_account: &name_key,
+        nft_record: &nft_record,
+        name_owner: &alice_key,
+        metadata_account: &metadata_key,
+        central_state: &central_key,
+        spl_token_program: &spl_token::ID,
+        metadata_program: &mpl_token_metadata::ID,
+        system_program: &system_program::ID,
+        spl_name_service_program: &spl_name_service::ID,
+        rent_account: &sysvar::rent::ID,
+        fee_payer: &payer,
+        edition_account: &edition_key,
+        collection_metadata: &collection_metadata_key,
+        collection_mint: &collection_mint,
+        nft_edition: &nft_edition_key,
+        nft_escrow: &nft_escrow,
+        ata_program: &spl_associated_token_account::ID,
+        instructions_sysvar: &sysvar::instructions::ID,
+        metadata_signer: &metadata_signer_key,
+        fee_source: &payer,
+        fee_vault: &central_key,
+        referrer: &central_key,
+        tld_record: None,
+        name_class: None,
+        parent_name: None,
+        parent_nft_record: None,
+        parent_nft_account: None,
+    };
+    let params = |uri: &str| create_nft::Params {
+        name: name.to_string(),
+        uri: uri.to_string(),
+        creator_share: 0,
+        soulbound: false,
+        wipe_data: false,
+    };
+    let expiry = i64::MAX;
+    let sign = |signer: &Keypair, uri: &str| -> [u8; 64] {
+        let message = create_nft::attestation_message(&name_key, uri, expiry);
+        signer.sign_message(&message).into()
+    };
+
+    // Missing attestation, the metadata signer does not co-sign either
+    let [_, ix] = create_nft_attested(
+        program_id,
+        accounts(),
+        params(uri),
+        expiry,
+        &sign(&metadata_signer, uri),
+    );
+    assert!(!ix.accounts[20].is_signer);
+    let err = sign_send_instructions(&mut prg_test_ctx, vec![ix.clone()], vec![&alice])
+        .await
+        .is_err();
+    assert!(err);
+
+    // Attestation signed by another key
+    let other_signer = Keypair::new();
+    let message = create_nft::attestation_message(&name_key, uri, expiry);
+    let ix_ed25519 =
+        ed25519_instruction(&other_signer.pubkey(), &sign(&other_signer, uri), &message);
+    let err = sign_send_instructions(&mut prg_test_ctx, vec![ix_ed25519, ix], vec![&alice])
+        .await
+        .is_err();
+    assert!(err);
+
+    // Attestation of another URI than the one of the instruction
+    let [ix_ed25519, _] = create_nft_attested(
+        program_id,
+        accounts(),
+        params(uri),
+        expiry,
+        &sign(&metadata_signer, uri),
+    );
+    let [_, ix] = create_nft_attested(
+        program_id,
+        accounts(),
+        params("https://example.com/tampered.json"),
+        expiry,
+        &sign(&metadata_signer, uri),
+    );
+    let err = sign_send_instructions(&mut prg_test_ctx, vec![ix_ed25519, ix], vec![&alice])
+        .await
+        .is_err();
+    assert!(err);
+
+    // Valid attestation
+    let ixs = create_nft_attested(
+        program_id,
+        accounts(),
+        params(uri),
+        expiry,
+        &sign(&metadata_signer, uri),
+    );
+    sign_send_instructions(&mut prg_test_ctx, ixs.to_vec(), vec![&alice])
+        .await
+        .unwrap();
+
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(name_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = NameRecordHeader::unpack_from_slice(&info.data).unwrap();
+    assert_eq!(header.owner, nft_record);
+    let info = prg_test_ctx
+        .banks_client
+        .get_account(metadata_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = Metadata::safe_deserialize(&info.data).unwrap();
+    assert_eq!(metadata.uri.trim_end_matches('\0'), uri);
+}
+
+#[test]
+fn test_metadata_name() {
+    let name = "a".repeat(32);
+    assert_eq!(create_nft::metadata_name(&name), name);
+
+    let name = "a".repeat(40);
+    assert_eq!(
+        create_nft::metadata_name(&name),
+        format!("{}...", "a".repeat(29))
+    );
+
+    // Multi-byte characters are never split
+    let name = "é".repeat(20);
+    let truncated = create_nft::metadata_name(&name);
+    assert_eq!(truncated, format!("{}...", "é".repeat(14)));
+    assert!(truncated.len() <= mpl_token_metadata::MAX_NAME_LENGTH);
+}
+
+#[test]
+fn test_config() {
+    assert_eq!(MAINNET.program_id, name_tokenizer::ID);
+    assert_eq!(MAINNET.central_state, name_tokenizer::central_state::KEY);
+
+    assert_eq!(
+        LOCALNET_ATTESTED.metadata_signer,
+        Some(attested_metadata_signer().pubkey())
+    );
+
+    for config in [&MAINNET, &DEVNET, &LOCALNET, &LOCALNET_ATTESTED].iter() {
+        let found = Config::from_program_id(&config.program_id).unwrap();
+        assert_eq!(found.program_id, config.program_id);
+
+        let (central_state, central_state_nonce) = CentralState::find_key(&config.program_id);
+        assert_eq!(config.central_state, central_state);
+        assert_eq!(config.central_state_nonce, central_state_nonce);
+
+        let (collection_mint, collection_nonce) = Pubkey::find_program_address(
+            &[COLLECTION_PREFIX, &config.program_id.to_bytes()],
+            &config.program_id,
+        );
+        assert_eq!(config.collection_mint, collection_mint);
+        assert_eq!(config.collection_nonce, collection_nonce);
+
+        let (_, collection_metadata_nonce) = Metadata::find_pda(&collection_mint);
+        let (_, collection_edition_nonce) = MasterEdition::find_pda(&collection_mint);
+        assert_eq!(config.collection_metadata_nonce, collection_metadata_nonce);
+        assert_eq!(config.collection_edition_nonce, collection_edition_nonce);
+    }
+
+    assert!(Config::from_program_id(&Pubkey::new_unique()).is_err());
+}
+
+#[test]
+fn test_nft_record_view() {
+    let key = Pubkey::new_unique();
+    let class = Pubkey::new_unique();
+    let nonces = NftRecordNonces {
+        mint: 255,
+        metadata: 254,
+        edition: 253,
+        escrow: 252,
+    };
+    let record = NftRecord::new(
+        254,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        class,
+        true,
+        nonces,
+    );
+    let data = record.try_to_vec().unwrap();
+    assert_eq!(data.len(), NftRecord::LEN);
+
+    for len in [NftRecord::LEN, NftRecord::LEGACY_LEN].iter() {
+        let mut data = data[..*len].to_vec();
+        let mut lamports = 0;
+        let account = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &LOCALNET.program_id,
+            false,
+            0,
+        );
+        assert!(NftRecordMut::from_account_info(&account, Tag::InactiveRecord).is_err());
+
+        {
+            let mut view = NftRecordMut::from_account_info(&account, Tag::ActiveRecord).unwrap();
+            assert!(view.is_active());
+            assert_eq!(view.base.nonce, record.nonce);
+            assert_eq!(view.base.name_account, record.name_account);
+            assert_eq!(view.base.owner, record.owner);
+            assert_eq!(view.base.nft_mint, record.nft_mint);
+            let legacy = *len == NftRecord::LEGACY_LEN;
+            assert_eq!(view.class(), if legacy { Pubkey::default() } else { class });
+            assert_eq!(view.soulbound(), !legacy);
+            let view_nonces = view
+                .nonces()
+                .map(|n| (n.mint, n.metadata, n.edition, n.escrow));
+            assert_eq!(view_nonces, (!legacy).then_some((255, 254, 253, 252)));
+
+            view.base.tag = Tag::InactiveRecord as u8;
+            view.base.owner = key;
+        }
+
+        let saved = NftRecord::from_account_info(&account, Tag::InactiveRecord).unwrap();
+        assert_eq!(saved.owner, key);
+        assert_eq!(saved.nft_mint, record.nft_mint);
+    }
+}
+
+#[test]
+fn test_central_state_view() {
+    let key = Pubkey::new_unique();
+    let fees = Fees {
+        mint: Pubkey::new_unique(),
+        tokenization_fee: 1_000_000,
+        redemption_fee: 2_000_000,
+        referrer_share: 10,
+    };
+    let central_state = CentralState::new(
+        "https://example.com/".to_string(),
+        UriDerivation::Mint,
+        20,
+        fees,
+        FeeVaultNonces {
+            authority: 254,
+            vault: 253,
+        },
+        vec![Pubkey::new_unique()],
+    );
+    let mut data = vec![0; central_state.borsh_len()];
+    central_state.save(&mut data).unwrap();
+    assert!(central_state.save(&mut data[..10]).is_err());
+
+    let mut lamports = 0;
+    let account = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &LOCALNET.program_id,
+        false,
+        0,
+    );
+    let view = CentralStateRef::from_account_info(&account).unwrap();
+    assert_eq!(view.base.uri_derivation, UriDerivation::Mint as u8);
+    assert_eq!(view.base.max_creator_share, 20);
+    let view_fees = view.fees();
+    assert_eq!(view_fees.mint, fees.mint);
+    assert_eq!(view_fees.tokenization_fee, fees.tokenization_fee);
+    assert_eq!(view_fees.redemption_fee, fees.redemption_fee);
+    assert_eq!(view_fees.referrer_share, fees.referrer_share);
+    let nonces = view.fee_vault_nonces();
+    assert_eq!((nonces.authority, nonces.vault), (254, 253));
+    drop(view);
+
+    let saved = CentralState::from_account_info(&account).unwrap();
+    assert_eq!(saved.base_uri, "https://example.com/");
+    assert_eq!(saved.allowed_programs, central_state.allowed_programs);
+
+    // Empty accounts are not central states or TLD records
+    let mut lamports = 0;
+    let mut empty = vec![];
+    let account = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut empty,
+        &LOCALNET.program_id,
+        false,
+        0,
+    );
+    assert!(CentralStateRef::from_account_info(&account).is_err());
+    assert!(CentralState::from_account_info(&account).is_err());
+    assert!(TldRecord::from_account_info(&account).is_err());
+}
+
+#[test]
+fn test_metadata_attestation() {
+    let metadata_signer = Pubkey::new_unique();
+    let name_account = Pubkey::new_unique();
+    let uri = "https://example.com/metadata.json";
+    let expiry = 1_700_000_000;
+    let message = create_nft::attestation_message(&name_account, uri, expiry);
+    let ix = ed25519_instruction(&metadata_signer, &[7; 64], &message);
+    assert_eq!(ix.program_id, ed25519_program::ID);
+
+    // Round trip
+    let (signer, parsed) = create_nft::parse_ed25519_instruction(&ix.data).unwrap();
+    assert_eq!(signer, metadata_signer.as_ref());
+    assert_eq!(parsed, &message[..]);
+    create_nft::verify_attestation(&ix.data, &metadata_signer, &name_account, uri, expiry).unwrap();
+
+    // The signature, public key and message must be read from the ed25519 instruction itself
+    for index_offset in [4, 8, 14].iter() {
+        let mut data = ix.data.clone();
+        data[*index_offset..*index_offset + 2].copy_from_slice(&0u16.to_le_bytes());
+        assert!(create_nft::parse_ed25519_instruction(&data).is_err());
+        assert!(create_nft::verify_attestation(
+            &data,
+            &metadata_signer,
+            &name_account,
+            uri,
+            expiry
+        )
+        .is_err());
+    }
+
+    // A single signature is expected
+    let mut data = ix.data.clone();
+    data[0] = 2;
+    assert!(create_nft::parse_ed25519_instruction(&data).is_err());
+
+    // Wrong signer
+    assert!(create_nft::verify_attestation(
+        &ix.data,
+        &Pubkey::new_unique(),
+        &name_account,
+        uri,
+        expiry
+    )
+    .is_err());
+
+    // Mismatched name account or URI
+    assert!(create_nft::verify_attestation(
+        &ix.data,
+        &metadata_signer,
+        &Pubkey::new_unique(),
+        uri,
+        expiry
+    )
+    .is_err());
+    for other_uri in ["https://example.com/metadata.jsom", "https://example.com/"].iter() {
+        assert!(create_nft::verify_attestation(
+            &ix.data,
+            &metadata_signer,
+            &name_account,
+            other_uri,
+            expiry
+        )
+        .is_err());
+    }
+
+    // Expired
+    assert!(create_nft::verify_attestation(
+        &ix.data,
+        &metadata_signer,
+        &name_account,
+        uri,
+        expiry + 1
+    )
+    .is_err());
+}
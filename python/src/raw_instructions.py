@@ -1,5 +1,5 @@
-from typing import List
-from borsh_construct import U8, String, CStruct
+from typing import List, Optional
+from borsh_construct import U8, U32, U64, Bool, String, Vec, Option, CStruct
 from solana.transaction import TransactionInstruction, AccountMeta
 from solana.publickey import PublicKey
 
@@ -33,6 +33,7 @@ class CreateCollectionInstruction:
         spl_name_service_program: PublicKey,
         ata_program: PublicKey,
         rent_account: PublicKey,
+        tld_record: Optional[PublicKey] = None,
     ) -> TransactionInstruction:
         data = self.serialize()
         keys: List[AccountMeta] = []
@@ -48,6 +49,8 @@ class CreateCollectionInstruction:
         keys.append(AccountMeta(spl_name_service_program, False, False))
         keys.append(AccountMeta(ata_program, False, False))
         keys.append(AccountMeta(rent_account, False, False))
+        if tld_record is not None:
+            keys.append(AccountMeta(tld_record, False, False))
         return TransactionInstruction(keys, programId, data)
 
 
@@ -93,18 +96,27 @@ class CreateNftInstruction:
         "tag" / U8,
         "name" / String,
         "uri" / String,
+        "creator_share" / U8,
+        "soulbound" / Bool,
+        "wipe_data" / Bool,
     )
 
     def serialize(
         self,
         name: str,
         uri: str,
+        creator_share: int,
+        soulbound: bool,
+        wipe_data: bool,
     ) -> str:
         return self.schema.build(
             {
                 "tag": 2,
                 "name": name,
                 "uri": uri,
+                "creator_share": creator_share,
+                "soulbound": soulbound,
+                "wipe_data": wipe_data,
             }
         )
 
@@ -127,13 +139,31 @@ class CreateNftInstruction:
         system_program: PublicKey,
         spl_name_service_program: PublicKey,
         rent_account: PublicKey,
+        nft_edition: PublicKey,
+        nft_escrow: PublicKey,
+        ata_program: PublicKey,
+        instructions_sysvar: PublicKey,
         metadata_signer: PublicKey,
+        fee_source: PublicKey,
+        fee_vault: PublicKey,
+        referrer: PublicKey,
         name: str,
         uri: str,
+        creator_share: int,
+        soulbound: bool,
+        wipe_data: bool,
+        tld_record: Optional[PublicKey] = None,
+        name_class: Optional[PublicKey] = None,
+        parent_name: Optional[PublicKey] = None,
+        parent_nft_record: Optional[PublicKey] = None,
+        parent_nft_account: Optional[PublicKey] = None,
     ) -> TransactionInstruction:
         data = self.serialize(
             name,
             uri,
+            creator_share,
+            soulbound,
+            wipe_data,
         )
         keys: List[AccountMeta] = []
         keys.append(AccountMeta(mint, False, True))
@@ -152,21 +182,58 @@ class CreateNftInstruction:
         keys.append(AccountMeta(system_program, False, False))
         keys.append(AccountMeta(spl_name_service_program, False, False))
         keys.append(AccountMeta(rent_account, False, False))
-        keys.append(AccountMeta(metadata_signer, True, False))
+        keys.append(AccountMeta(nft_edition, False, True))
+        keys.append(AccountMeta(nft_escrow, False, True))
+        keys.append(AccountMeta(ata_program, False, False))
+        keys.append(AccountMeta(instructions_sysvar, False, False))
+        keys.append(AccountMeta(metadata_signer, False, False))
+        keys.append(AccountMeta(fee_source, False, True))
+        keys.append(AccountMeta(fee_vault, False, True))
+        keys.append(AccountMeta(referrer, False, True))
+        if (
+            tld_record is not None
+            or name_class is not None
+            or parent_name is not None
+            or parent_nft_record is not None
+            or parent_nft_account is not None
+        ):
+            keys.append(AccountMeta(tld_record or programId, False, False))
+        if (
+            name_class is not None
+            or parent_name is not None
+            or parent_nft_record is not None
+            or parent_nft_account is not None
+        ):
+            keys.append(
+                AccountMeta(name_class or programId, name_class is not None, False)
+            )
+        if (
+            parent_name is not None
+            or parent_nft_record is not None
+            or parent_nft_account is not None
+        ):
+            keys.append(AccountMeta(parent_name or programId, False, False))
+        if parent_nft_record is not None or parent_nft_account is not None:
+            keys.append(AccountMeta(parent_nft_record or programId, False, False))
+        if parent_nft_account is not None:
+            keys.append(AccountMeta(parent_nft_account, False, False))
         return TransactionInstruction(keys, programId, data)
 
 
 class RedeemNftInstruction:
     schema = CStruct(
         "tag" / U8,
+        "wipe_data" / Bool,
     )
 
     def serialize(
         self,
+        wipe_data: bool,
     ) -> str:
         return self.schema.build(
             {
                 "tag": 3,
+                "wipe_data": wipe_data,
             }
         )
 
@@ -180,8 +247,20 @@ class RedeemNftInstruction:
         name_account: PublicKey,
         spl_token_program: PublicKey,
         spl_name_service_program: PublicKey,
+        nft_escrow: PublicKey,
+        central_state: PublicKey,
+        system_program: PublicKey,
+        fee_source: PublicKey,
+        fee_vault: PublicKey,
+        referrer: PublicKey,
+        nft_edition: PublicKey,
+        metadata_program: PublicKey,
+        wipe_data: bool,
+        name_class: Optional[PublicKey] = None,
     ) -> TransactionInstruction:
-        data = self.serialize()
+        data = self.serialize(
+            wipe_data,
+        )
         keys: List[AccountMeta] = []
         keys.append(AccountMeta(mint, False, True))
         keys.append(AccountMeta(nft_source, False, True))
@@ -190,6 +269,16 @@ class RedeemNftInstruction:
         keys.append(AccountMeta(name_account, False, True))
         keys.append(AccountMeta(spl_token_program, False, False))
         keys.append(AccountMeta(spl_name_service_program, False, False))
+        keys.append(AccountMeta(nft_escrow, False, True))
+        keys.append(AccountMeta(central_state, False, True))
+        keys.append(AccountMeta(system_program, False, False))
+        keys.append(AccountMeta(fee_source, False, True))
+        keys.append(AccountMeta(fee_vault, False, True))
+        keys.append(AccountMeta(referrer, False, True))
+        keys.append(AccountMeta(nft_edition, False, False))
+        keys.append(AccountMeta(metadata_program, False, False))
+        if name_class is not None:
+            keys.append(AccountMeta(name_class, True, False))
         return TransactionInstruction(keys, programId, data)
 
 
@@ -261,6 +350,7 @@ class EditDataInstruction:
         spl_name_service_program: PublicKey,
         offset: int,
         data: List[int],
+        name_class: Optional[PublicKey] = None,
     ) -> TransactionInstruction:
         data = self.serialize(
             offset,
@@ -273,4 +363,630 @@ class EditDataInstruction:
         keys.append(AccountMeta(name_account, False, True))
         keys.append(AccountMeta(spl_token_program, False, False))
         keys.append(AccountMeta(spl_name_service_program, False, False))
+        if name_class is not None:
+            keys.append(AccountMeta(name_class, True, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class UnverifyNftInstruction:
+    schema = CStruct(
+        "tag" / U8,
+    )
+
+    def serialize(
+        self,
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 6,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        metadata_account: PublicKey,
+        edition_account: PublicKey,
+        collection_metadata: PublicKey,
+        collection_mint: PublicKey,
+        central_state: PublicKey,
+        fee_payer: PublicKey,
+        metadata_program: PublicKey,
+        system_program: PublicKey,
+        rent_account: PublicKey,
+        metadata_signer: PublicKey,
+        tld_record: Optional[PublicKey] = None,
+    ) -> TransactionInstruction:
+        data = self.serialize()
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(metadata_account, False, True))
+        keys.append(AccountMeta(edition_account, False, False))
+        keys.append(AccountMeta(collection_metadata, False, False))
+        keys.append(AccountMeta(collection_mint, False, False))
+        keys.append(AccountMeta(central_state, False, True))
+        keys.append(AccountMeta(fee_payer, True, True))
+        keys.append(AccountMeta(metadata_program, False, False))
+        keys.append(AccountMeta(system_program, False, False))
+        keys.append(AccountMeta(rent_account, False, False))
+        keys.append(AccountMeta(metadata_signer, True, False))
+        if tld_record is not None:
+            keys.append(AccountMeta(tld_record, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class RegisterTldInstruction:
+    schema = CStruct(
+        "tag" / U8,
+        "collection_name" / String,
+        "collection_uri" / String,
+        "symbol" / String,
+    )
+
+    def serialize(
+        self,
+        collection_name: str,
+        collection_uri: str,
+        symbol: str,
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 7,
+                "collection_name": collection_name,
+                "collection_uri": collection_uri,
+                "symbol": symbol,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        tld_record: PublicKey,
+        tld: PublicKey,
+        program_data: PublicKey,
+        admin: PublicKey,
+        fee_payer: PublicKey,
+        system_program: PublicKey,
+        collection_name: str,
+        collection_uri: str,
+        symbol: str,
+    ) -> TransactionInstruction:
+        data = self.serialize(
+            collection_name,
+            collection_uri,
+            symbol,
+        )
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(tld_record, False, True))
+        keys.append(AccountMeta(tld, False, False))
+        keys.append(AccountMeta(program_data, False, False))
+        keys.append(AccountMeta(admin, True, False))
+        keys.append(AccountMeta(fee_payer, True, True))
+        keys.append(AccountMeta(system_program, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class UpdateCentralStateInstruction:
+    schema = CStruct(
+        "tag" / U8,
+        "base_uri" / String,
+        "uri_derivation" / U8,
+        "max_creator_share" / U8,
+        "fees" / CStruct(
+            "mint" / U8[32],
+            "tokenization_fee" / U64,
+            "redemption_fee" / U64,
+            "referrer_share" / U8,
+        ),
+        "allowed_programs" / Vec(U8[32]),
+    )
+
+    def serialize(
+        self,
+        base_uri: str,
+        uri_derivation: int,
+        max_creator_share: int,
+        fees: dict,
+        allowed_programs: List[List[int]],
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 8,
+                "base_uri": base_uri,
+                "uri_derivation": uri_derivation,
+                "max_creator_share": max_creator_share,
+                "fees": fees,
+                "allowed_programs": allowed_programs,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        central_state: PublicKey,
+        program_data: PublicKey,
+        admin: PublicKey,
+        fee_payer: PublicKey,
+        system_program: PublicKey,
+        base_uri: str,
+        uri_derivation: int,
+        max_creator_share: int,
+        fees: dict,
+        allowed_programs: List[List[int]],
+    ) -> TransactionInstruction:
+        data = self.serialize(
+            base_uri,
+            uri_derivation,
+            max_creator_share,
+            fees,
+            allowed_programs,
+        )
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(central_state, False, True))
+        keys.append(AccountMeta(program_data, False, False))
+        keys.append(AccountMeta(admin, True, False))
+        keys.append(AccountMeta(fee_payer, True, True))
+        keys.append(AccountMeta(system_program, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class EditCreatorsInstruction:
+    schema = CStruct(
+        "tag" / U8,
+        "creator" / U8[32],
+        "share" / U8,
+    )
+
+    def serialize(
+        self,
+        creator: List[int],
+        share: int,
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 9,
+                "creator": creator,
+                "share": share,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        nft_owner: PublicKey,
+        nft_account: PublicKey,
+        nft_record: PublicKey,
+        name_account: PublicKey,
+        metadata_account: PublicKey,
+        central_state: PublicKey,
+        metadata_program: PublicKey,
+        creator: List[int],
+        share: int,
+    ) -> TransactionInstruction:
+        data = self.serialize(
+            creator,
+            share,
+        )
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(nft_owner, True, False))
+        keys.append(AccountMeta(nft_account, False, False))
+        keys.append(AccountMeta(nft_record, False, False))
+        keys.append(AccountMeta(name_account, False, False))
+        keys.append(AccountMeta(metadata_account, False, True))
+        keys.append(AccountMeta(central_state, False, False))
+        keys.append(AccountMeta(metadata_program, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class WithdrawFeesInstruction:
+    schema = CStruct(
+        "tag" / U8,
+    )
+
+    def serialize(
+        self,
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 10,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        central_state: PublicKey,
+        fee_vault: PublicKey,
+        destination: PublicKey,
+        program_data: PublicKey,
+        admin: PublicKey,
+        spl_token_program: PublicKey,
+        fee_vault_authority: Optional[PublicKey] = None,
+    ) -> TransactionInstruction:
+        data = self.serialize()
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(central_state, False, True))
+        keys.append(AccountMeta(fee_vault, False, True))
+        keys.append(AccountMeta(destination, False, True))
+        keys.append(AccountMeta(program_data, False, False))
+        keys.append(AccountMeta(admin, True, False))
+        keys.append(AccountMeta(spl_token_program, False, False))
+        if fee_vault_authority is not None:
+            keys.append(AccountMeta(fee_vault_authority, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class SetLockAuthorityInstruction:
+    schema = CStruct(
+        "tag" / U8,
+        "lock_authority" / U8[32],
+    )
+
+    def serialize(
+        self,
+        lock_authority: List[int],
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 11,
+                "lock_authority": lock_authority,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        nft_owner: PublicKey,
+        nft_account: PublicKey,
+        nft_record: PublicKey,
+        name_account: PublicKey,
+        lock_record: PublicKey,
+        central_state: PublicKey,
+        fee_payer: PublicKey,
+        spl_token_program: PublicKey,
+        system_program: PublicKey,
+        lock_authority: List[int],
+    ) -> TransactionInstruction:
+        data = self.serialize(
+            lock_authority,
+        )
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(nft_owner, True, False))
+        keys.append(AccountMeta(nft_account, False, True))
+        keys.append(AccountMeta(nft_record, False, False))
+        keys.append(AccountMeta(name_account, False, False))
+        keys.append(AccountMeta(lock_record, False, True))
+        keys.append(AccountMeta(central_state, False, False))
+        keys.append(AccountMeta(fee_payer, True, True))
+        keys.append(AccountMeta(spl_token_program, False, False))
+        keys.append(AccountMeta(system_program, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class SetLockInstruction:
+    schema = CStruct(
+        "tag" / U8,
+        "locked" / Bool,
+    )
+
+    def serialize(
+        self,
+        locked: bool,
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 12,
+                "locked": locked,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        lock_authority: PublicKey,
+        lock_record: PublicKey,
+        nft_record: PublicKey,
+        nft_account: PublicKey,
+        mint: PublicKey,
+        nft_edition: PublicKey,
+        central_state: PublicKey,
+        spl_token_program: PublicKey,
+        metadata_program: PublicKey,
+        locked: bool,
+    ) -> TransactionInstruction:
+        data = self.serialize(
+            locked,
+        )
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(lock_authority, True, False))
+        keys.append(AccountMeta(lock_record, False, False))
+        keys.append(AccountMeta(nft_record, False, False))
+        keys.append(AccountMeta(nft_account, False, True))
+        keys.append(AccountMeta(mint, False, False))
+        keys.append(AccountMeta(nft_edition, False, False))
+        keys.append(AccountMeta(central_state, False, False))
+        keys.append(AccountMeta(spl_token_program, False, False))
+        keys.append(AccountMeta(metadata_program, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class ManageDataInstruction:
+    schema = CStruct(
+        "tag" / U8,
+        "space" / Option(U32),
+        "clears" / Vec(CStruct("offset" / U32, "len" / U32)),
+        "writes" / Vec(CStruct("offset" / U32, "data" / Vec(U8))),
+    )
+
+    def serialize(
+        self,
+        space: Optional[int],
+        clears: List[dict],
+        writes: List[dict],
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 13,
+                "space": space,
+                "clears": clears,
+                "writes": writes,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        nft_owner: PublicKey,
+        nft_account: PublicKey,
+        nft_record: PublicKey,
+        name_account: PublicKey,
+        spl_token_program: PublicKey,
+        spl_name_service_program: PublicKey,
+        fee_payer: PublicKey,
+        system_program: PublicKey,
+        space: Optional[int],
+        clears: List[dict],
+        writes: List[dict],
+        name_class: Optional[PublicKey] = None,
+    ) -> TransactionInstruction:
+        data = self.serialize(
+            space,
+            clears,
+            writes,
+        )
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(nft_owner, True, False))
+        keys.append(AccountMeta(nft_account, False, False))
+        keys.append(AccountMeta(nft_record, False, False))
+        keys.append(AccountMeta(name_account, False, True))
+        keys.append(AccountMeta(spl_token_program, False, False))
+        keys.append(AccountMeta(spl_name_service_program, False, False))
+        keys.append(AccountMeta(fee_payer, True, True))
+        keys.append(AccountMeta(system_program, False, False))
+        if name_class is not None:
+            keys.append(AccountMeta(name_class, True, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class ExecuteInstruction:
+    schema = CStruct(
+        "tag" / U8,
+        "data" / Vec(U8),
+    )
+
+    def serialize(
+        self,
+        data: List[int],
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 14,
+                "data": data,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        nft_owner: PublicKey,
+        nft_account: PublicKey,
+        nft_record: PublicKey,
+        central_state: PublicKey,
+        target_program: PublicKey,
+        data: List[int],
+    ) -> TransactionInstruction:
+        data = self.serialize(
+            data,
+        )
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(nft_owner, True, False))
+        keys.append(AccountMeta(nft_account, False, False))
+        keys.append(AccountMeta(nft_record, False, False))
+        keys.append(AccountMeta(central_state, False, False))
+        keys.append(AccountMeta(target_program, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class SyncOwnerInstruction:
+    schema = CStruct(
+        "tag" / U8,
+    )
+
+    def serialize(
+        self,
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 15,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        nft_record: PublicKey,
+        nft_account: PublicKey,
+    ) -> TransactionInstruction:
+        data = self.serialize()
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(nft_record, False, True))
+        keys.append(AccountMeta(nft_account, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class CreateMintAndNftInstruction:
+    schema = CStruct(
+        "tag" / U8,
+        "name" / String,
+        "uri" / String,
+        "creator_share" / U8,
+        "soulbound" / Bool,
+        "wipe_data" / Bool,
+    )
+
+    def serialize(
+        self,
+        name: str,
+        uri: str,
+        creator_share: int,
+        soulbound: bool,
+        wipe_data: bool,
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 16,
+                "name": name,
+                "uri": uri,
+                "creator_share": creator_share,
+                "soulbound": soulbound,
+                "wipe_data": wipe_data,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        mint: PublicKey,
+        nft_destination: PublicKey,
+        name_account: PublicKey,
+        nft_record: PublicKey,
+        name_owner: PublicKey,
+        metadata_account: PublicKey,
+        edition_account: PublicKey,
+        collection_metadata: PublicKey,
+        collection_mint: PublicKey,
+        central_state: PublicKey,
+        fee_payer: PublicKey,
+        spl_token_program: PublicKey,
+        metadata_program: PublicKey,
+        system_program: PublicKey,
+        spl_name_service_program: PublicKey,
+        rent_account: PublicKey,
+        nft_edition: PublicKey,
+        nft_escrow: PublicKey,
+        ata_program: PublicKey,
+        instructions_sysvar: PublicKey,
+        metadata_signer: PublicKey,
+        fee_source: PublicKey,
+        fee_vault: PublicKey,
+        referrer: PublicKey,
+        name: str,
+        uri: str,
+        creator_share: int,
+        soulbound: bool,
+        wipe_data: bool,
+        tld_record: Optional[PublicKey] = None,
+        name_class: Optional[PublicKey] = None,
+        parent_name: Optional[PublicKey] = None,
+        parent_nft_record: Optional[PublicKey] = None,
+        parent_nft_account: Optional[PublicKey] = None,
+    ) -> TransactionInstruction:
+        data = self.serialize(
+            name,
+            uri,
+            creator_share,
+            soulbound,
+            wipe_data,
+        )
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(mint, False, True))
+        keys.append(AccountMeta(nft_destination, False, True))
+        keys.append(AccountMeta(name_account, False, True))
+        keys.append(AccountMeta(nft_record, False, True))
+        keys.append(AccountMeta(name_owner, True, True))
+        keys.append(AccountMeta(metadata_account, False, True))
+        keys.append(AccountMeta(edition_account, False, False))
+        keys.append(AccountMeta(collection_metadata, False, False))
+        keys.append(AccountMeta(collection_mint, False, False))
+        keys.append(AccountMeta(central_state, False, True))
+        keys.append(AccountMeta(fee_payer, True, True))
+        keys.append(AccountMeta(spl_token_program, False, False))
+        keys.append(AccountMeta(metadata_program, False, False))
+        keys.append(AccountMeta(system_program, False, False))
+        keys.append(AccountMeta(spl_name_service_program, False, False))
+        keys.append(AccountMeta(rent_account, False, False))
+        keys.append(AccountMeta(nft_edition, False, True))
+        keys.append(AccountMeta(nft_escrow, False, True))
+        keys.append(AccountMeta(ata_program, False, False))
+        keys.append(AccountMeta(instructions_sysvar, False, False))
+        keys.append(AccountMeta(metadata_signer, False, False))
+        keys.append(AccountMeta(fee_source, False, True))
+        keys.append(AccountMeta(fee_vault, False, True))
+        keys.append(AccountMeta(referrer, False, True))
+        if (
+            tld_record is not None
+            or name_class is not None
+            or parent_name is not None
+            or parent_nft_record is not None
+            or parent_nft_account is not None
+        ):
+            keys.append(AccountMeta(tld_record or programId, False, False))
+        if (
+            name_class is not None
+            or parent_name is not None
+            or parent_nft_record is not None
+            or parent_nft_account is not None
+        ):
+            keys.append(
+                AccountMeta(name_class or programId, name_class is not None, False)
+            )
+        if (
+            parent_name is not None
+            or parent_nft_record is not None
+            or parent_nft_account is not None
+        ):
+            keys.append(AccountMeta(parent_name or programId, False, False))
+        if parent_nft_record is not None or parent_nft_account is not None:
+            keys.append(AccountMeta(parent_nft_record or programId, False, False))
+        if parent_nft_account is not None:
+            keys.append(AccountMeta(parent_nft_account, False, False))
+        return TransactionInstruction(keys, programId, data)
+
+
+class GetEffectiveOwnerInstruction:
+    schema = CStruct(
+        "tag" / U8,
+    )
+
+    def serialize(
+        self,
+    ) -> str:
+        return self.schema.build(
+            {
+                "tag": 17,
+            }
+        )
+
+    def getInstruction(
+        self,
+        programId: PublicKey,
+        name_account: PublicKey,
+        nft_record: PublicKey,
+        nft_account: Optional[PublicKey] = None,
+    ) -> TransactionInstruction:
+        data = self.serialize()
+        keys: List[AccountMeta] = []
+        keys.append(AccountMeta(name_account, False, False))
+        keys.append(AccountMeta(nft_record, False, False))
+        if nft_account is not None:
+            keys.append(AccountMeta(nft_account, False, False))
         return TransactionInstruction(keys, programId, data)